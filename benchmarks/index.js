@@ -18,6 +18,10 @@ import wbindgen_init, {
   call_foo_bar_final_n_times as wbindgen_call_foo_bar_final_n_times,
   call_foo_bar_structural_n_times as wbindgen_call_foo_bar_structural_n_times,
   str_roundtrip as wbindgen_str_roundtrip,
+  serde_json_to_js as wbindgen_serde_json_to_js,
+  serde_direct_to_js as wbindgen_serde_direct_to_js,
+  serde_json_from_js as wbindgen_serde_json_from_js,
+  serde_direct_from_js as wbindgen_serde_direct_from_js,
 } from './pkg/wasm_bindgen_benchmark.js';
 import {
   call_js_thunk_n_times as js_call_js_thunk_n_times,
@@ -81,6 +85,11 @@ function makeBenchmarks() {
   benchmarks.wbindgen_call_foo_bar_final_n_times = () => wbindgen_call_foo_bar_final_n_times(10000, foo);
   benchmarks.wbindgen_call_foo_bar_structural_n_times = () => wbindgen_call_foo_bar_structural_n_times(10000, foo);
 
+  const records = wbindgen_serde_json_to_js();
+  benchmarks.wbindgen_serde_json_to_js = wbindgen_serde_json_to_js;
+  benchmarks.wbindgen_serde_direct_to_js = wbindgen_serde_direct_to_js;
+  benchmarks.wbindgen_serde_json_from_js = () => wbindgen_serde_json_from_js(records);
+  benchmarks.wbindgen_serde_direct_from_js = () => wbindgen_serde_direct_from_js(records);
 
   const strings = {
     ascii_small: 'ja',
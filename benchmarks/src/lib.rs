@@ -1,3 +1,6 @@
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
 extern crate wasm_bindgen;
 extern crate web_sys;
 
@@ -179,3 +182,47 @@ pub fn count_node_types(element: Node) {
 pub fn str_roundtrip(s: String) -> String {
     s
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct SerdeRecord {
+    id: u32,
+    name: String,
+    score: f64,
+    active: bool,
+    tags: Vec<String>,
+}
+
+thread_local! {
+    static SERDE_RECORDS: Vec<SerdeRecord> = (0..100)
+        .map(|i| SerdeRecord {
+            id: i,
+            name: format!("record {}", i),
+            score: f64::from(i) / 3.0,
+            active: i % 2 == 0,
+            tags: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        })
+        .collect();
+}
+
+#[wasm_bindgen]
+pub fn serde_json_to_js() -> JsValue {
+    SERDE_RECORDS.with(|records| JsValue::from_serde(records).unwrap())
+}
+
+#[wasm_bindgen]
+pub fn serde_direct_to_js() -> JsValue {
+    SERDE_RECORDS.with(|records| JsValue::from_serde_direct(records).unwrap())
+}
+
+#[wasm_bindgen]
+pub fn serde_json_from_js(records: &JsValue) -> usize {
+    records.into_serde::<Vec<SerdeRecord>>().unwrap().len()
+}
+
+#[wasm_bindgen]
+pub fn serde_direct_from_js(records: &JsValue) -> usize {
+    records
+        .into_serde_direct::<Vec<SerdeRecord>>()
+        .unwrap()
+        .len()
+}
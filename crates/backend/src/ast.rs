@@ -17,6 +17,8 @@ pub struct Program {
     pub enums: Vec<Enum>,
     /// rust structs
     pub structs: Vec<Struct>,
+    /// rust traits exported as classes wrapping trait objects
+    pub traits: Vec<Trait>,
     /// rust consts
     pub consts: Vec<Const>,
     /// "dictionaries", generated for WebIDL, which are basically just "typed
@@ -44,8 +46,8 @@ pub struct Export {
     pub method_kind: MethodKind,
     /// The type of `self` (either `self`, `&self`, or `&mut self`)
     pub method_self: Option<MethodSelf>,
-    /// The struct name, in Rust, this is attached to
-    pub rust_class: Option<Ident>,
+    /// The type, in Rust, this is attached to
+    pub rust_class: Option<syn::Type>,
     /// The name of the rust function/method on the rust side.
     pub rust_name: Ident,
     /// Whether or not this function should be flagged as the wasm start
@@ -161,6 +163,26 @@ pub enum OperationKind {
     IndexingGetter,
     IndexingSetter,
     IndexingDeleter,
+    Hook(ClassHook),
+}
+
+/// A well-known method name or symbol of JS classes that an exported method
+/// can be installed as.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Copy, Clone)]
+pub enum ClassHook {
+    /// `[Symbol.iterator]`
+    Iterator,
+    /// `[Symbol.asyncIterator]`
+    AsyncIterator,
+    /// `[Symbol.toPrimitive]`
+    ToPrimitive,
+    /// `valueOf`
+    ValueOf,
+    /// `toString`
+    ToString,
+    /// `[Symbol.for('nodejs.util.inspect.custom')]`
+    Inspect,
 }
 
 #[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
@@ -217,7 +239,7 @@ pub struct Function {
 #[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
 #[derive(Clone)]
 pub struct Struct {
-    pub rust_name: Ident,
+    pub rust_name: syn::Type,
     pub js_name: String,
     pub fields: Vec<StructField>,
     pub comments: Vec<String>,
@@ -227,7 +249,7 @@ pub struct Struct {
 #[derive(Clone)]
 pub struct StructField {
     pub name: syn::Member,
-    pub struct_name: Ident,
+    pub struct_name: syn::Type,
     pub readonly: bool,
     pub ty: syn::Type,
     pub getter: Ident,
@@ -235,6 +257,26 @@ pub struct StructField {
     pub comments: Vec<String>,
 }
 
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct Trait {
+    pub rust_name: Ident,
+    pub js_name: String,
+    /// The imported type implementing the trait on top of arbitrary JS objects
+    pub adapter: Ident,
+    pub methods: Vec<TraitMethod>,
+    pub comments: Vec<String>,
+}
+
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+#[derive(Clone)]
+pub struct TraitMethod {
+    pub rust_name: Ident,
+    pub method_self: MethodSelf,
+    pub arguments: Vec<syn::ArgCaptured>,
+    pub ret: Option<syn::Type>,
+}
+
 #[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
 #[derive(Clone)]
 pub struct Enum {
@@ -32,6 +32,9 @@ impl TryToTokens for ast::Program {
         for s in self.structs.iter() {
             s.to_tokens(tokens);
         }
+        for t in self.traits.iter() {
+            t.to_tokens(tokens);
+        }
         let mut types = HashSet::new();
         for i in self.imports.iter() {
             if let ast::ImportKind::Type(t) = &i.kind {
@@ -139,11 +142,76 @@ impl TryToTokens for ast::Program {
 impl ToTokens for ast::Struct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = &self.rust_name;
-        let name_str = self.js_name.to_string();
+        ExportedClass(quote! { #name }, &self.js_name).to_tokens(tokens);
+
+        for field in self.fields.iter() {
+            field.to_tokens(tokens);
+        }
+    }
+}
+
+impl ToTokens for ast::Trait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.rust_name;
+        let adapter = &self.adapter;
+        let class = quote! { wasm_bindgen::__rt::std::boxed::Box<dyn #name> };
+        ExportedClass(class, &self.js_name).to_tokens(tokens);
+
+        // The adapter's imported methods have the same names as the trait's
+        // methods, so implementing the trait is just a matter of forwarding
+        // each method to its structural import.
+        let methods = self.methods.iter().map(|method| {
+            let rust_name = &method.rust_name;
+            let receiver = match method.method_self {
+                ast::MethodSelf::RefMutable => quote! { &mut self },
+                _ => quote! { &self },
+            };
+            let arguments = &method.arguments;
+            let argument_names = method.arguments.iter().map(|arg| &arg.pat);
+            let ret = match &method.ret {
+                Some(ty) => quote! { -> #ty },
+                None => quote!(),
+            };
+            quote! {
+                fn #rust_name(#receiver, #(#arguments),*) #ret {
+                    #adapter::#rust_name(self, #(#argument_names),*)
+                }
+            }
+        });
+
+        (quote! {
+            #[allow(clippy::all)]
+            impl #name for #adapter {
+                #(#methods)*
+            }
+
+            #[allow(clippy::all)]
+            impl wasm_bindgen::__rt::core::convert::From<#adapter> for
+                wasm_bindgen::__rt::std::boxed::Box<dyn #name>
+            {
+                fn from(value: #adapter) -> Self {
+                    wasm_bindgen::__rt::std::boxed::Box::new(value)
+                }
+            }
+        })
+        .to_tokens(tokens);
+    }
+}
+
+/// The conversions shared by everything exported to JS as a class: the value
+/// is boxed up in a `WasmRefCell` and JS holds on to the raw pointer.
+struct ExportedClass<'a>(TokenStream, &'a str);
+
+impl<'a> ToTokens for ExportedClass<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.0;
+        let name_str = self.1.to_string();
         let name_len = name_str.len() as u32;
         let name_chars = name_str.chars().map(|c| c as u32);
         let new_fn = Ident::new(&shared::new_function(&name_str), Span::call_site());
         let free_fn = Ident::new(&shared::free_function(&name_str), Span::call_site());
+        let unwrap_fn = Ident::new(&shared::unwrap_function(&name_str), Span::call_site());
+        let expected = format!("an instance of `{}`", name_str);
         (quote! {
             #[allow(clippy::all)]
             impl wasm_bindgen::describe::WasmDescribe for #name {
@@ -224,6 +292,62 @@ impl ToTokens for ast::Struct {
                 }
             }
 
+            #[allow(clippy::all)]
+            impl wasm_bindgen::TryFromJsValue for #name {
+                fn try_from_js_value(value: wasm_bindgen::JsValue)
+                    -> wasm_bindgen::__rt::core::result::Result<
+                        Self,
+                        wasm_bindgen::TryFromJsValueError,
+                    >
+                {
+                    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+                    #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+                    extern "C" {
+                        fn #unwrap_fn(idx: u32) -> u32;
+                    }
+
+                    #[cfg(not(all(target_arch = "wasm32", not(target_os = "emscripten"))))]
+                    unsafe fn #unwrap_fn(_: u32) -> u32 {
+                        panic!("cannot convert from JsValue outside of the wasm target")
+                    }
+
+                    let ptr = unsafe {
+                        #unwrap_fn(wasm_bindgen::convert::IntoWasmAbi::into_abi(
+                            &value,
+                            &mut wasm_bindgen::convert::GlobalStack::new(),
+                        ))
+                    };
+                    if ptr == 0 {
+                        return wasm_bindgen::__rt::core::result::Result::Err(
+                            wasm_bindgen::TryFromJsValueError::new(value, #expected),
+                        );
+                    }
+                    unsafe {
+                        wasm_bindgen::__rt::core::result::Result::Ok(
+                            <#name as wasm_bindgen::convert::FromWasmAbi>::from_abi(
+                                ptr,
+                                &mut wasm_bindgen::convert::GlobalStack::new(),
+                            )
+                        )
+                    }
+                }
+            }
+
+            #[allow(clippy::all)]
+            impl wasm_bindgen::__rt::core::convert::TryFrom<wasm_bindgen::JsValue> for #name {
+                type Error = wasm_bindgen::TryFromJsValueError;
+
+                #[inline]
+                fn try_from(value: wasm_bindgen::JsValue)
+                    -> wasm_bindgen::__rt::core::result::Result<
+                        Self,
+                        wasm_bindgen::TryFromJsValueError,
+                    >
+                {
+                    <#name as wasm_bindgen::TryFromJsValue>::try_from_js_value(value)
+                }
+            }
+
             #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
             #[no_mangle]
             #[doc(hidden)]
@@ -277,10 +401,6 @@ impl ToTokens for ast::Struct {
 
         })
         .to_tokens(tokens);
-
-        for field in self.fields.iter() {
-            field.to_tokens(tokens);
-        }
     }
 }
 
@@ -414,7 +534,7 @@ impl TryToTokens for ast::Export {
                 quote! { me.#name }
             }
             None => match &self.rust_class {
-                Some(class) => quote! { #class::#name },
+                Some(class) => quote! { <#class>::#name },
                 None => quote! { #name },
             },
         };
@@ -130,6 +130,7 @@ fn shared_program<'a>(
             .structs
             .iter()
             .map(|a| shared_struct(a, intern))
+            .chain(prog.traits.iter().map(|a| shared_trait(a, intern)))
             .collect(),
         enums: prog.enums.iter().map(|a| shared_enum(a, intern)).collect(),
         imports: prog
@@ -309,6 +310,16 @@ fn shared_struct<'a>(s: &'a ast::Struct, intern: &'a Interner) -> Struct<'a> {
     }
 }
 
+// Exported traits show up in JS as a class with methods but no fields, which
+// is exactly what an exported struct without public fields looks like.
+fn shared_trait<'a>(t: &'a ast::Trait, _intern: &'a Interner) -> Struct<'a> {
+    Struct {
+        name: &t.js_name,
+        fields: Vec::new(),
+        comments: t.comments.iter().map(|s| &**s).collect(),
+    }
+}
+
 fn shared_struct_field<'a>(s: &'a ast::StructField, intern: &'a Interner) -> StructField<'a> {
     StructField {
         name: match &s.name {
@@ -515,6 +526,14 @@ fn from_ast_method_kind<'a>(
                 ast::OperationKind::IndexingGetter => OperationKind::IndexingGetter,
                 ast::OperationKind::IndexingSetter => OperationKind::IndexingSetter,
                 ast::OperationKind::IndexingDeleter => OperationKind::IndexingDeleter,
+                ast::OperationKind::Hook(hook) => OperationKind::Hook(match hook {
+                    ast::ClassHook::Iterator => ClassHook::Iterator,
+                    ast::ClassHook::AsyncIterator => ClassHook::AsyncIterator,
+                    ast::ClassHook::ToPrimitive => ClassHook::ToPrimitive,
+                    ast::ClassHook::ValueOf => ClassHook::ValueOf,
+                    ast::ClassHook::ToString => ClassHook::ToString,
+                    ast::ClassHook::Inspect => ClassHook::Inspect,
+                }),
             };
             MethodKind::Operation(Operation { is_static, kind })
         }
@@ -361,6 +361,43 @@ impl Descriptor {
             self,
         );
     }
+
+    /// Reconstructs the Rust type this descriptor came from, for use in
+    /// messages. This is only approximate as some types, such as enums and
+    /// `Result`, aren't recorded in descriptors.
+    pub fn rust_type(&self) -> String {
+        match self {
+            Descriptor::I8 => "i8".to_string(),
+            Descriptor::U8 | Descriptor::ClampedU8 => "u8".to_string(),
+            Descriptor::I16 => "i16".to_string(),
+            Descriptor::U16 => "u16".to_string(),
+            Descriptor::I32 => "i32".to_string(),
+            Descriptor::U32 => "u32".to_string(),
+            Descriptor::I64 => "i64".to_string(),
+            Descriptor::U64 => "u64".to_string(),
+            Descriptor::F32 => "f32".to_string(),
+            Descriptor::F64 => "f64".to_string(),
+            Descriptor::Boolean => "bool".to_string(),
+            Descriptor::Char => "char".to_string(),
+            Descriptor::String => "String".to_string(),
+            Descriptor::Anyref => "JsValue".to_string(),
+            Descriptor::Enum { .. } => "u32".to_string(),
+            Descriptor::Unit => "()".to_string(),
+            Descriptor::RustStruct(name) => name.clone(),
+            Descriptor::Function(f) => {
+                format!("fn({}){}", f.rust_arguments(), f.rust_return())
+            }
+            Descriptor::Closure(c) => c.rust_type(),
+            Descriptor::Ref(d) => match &**d {
+                Descriptor::String => "&str".to_string(),
+                d => format!("&{}", d.rust_type()),
+            },
+            Descriptor::RefMut(d) => format!("&mut {}", d.rust_type()),
+            Descriptor::Slice(d) => format!("[{}]", d.rust_type()),
+            Descriptor::Vector(d) => format!("Vec<{}>", d.rust_type()),
+            Descriptor::Option(d) => format!("Option<{}>", d.rust_type()),
+        }
+    }
 }
 
 fn get(a: &mut &[u32]) -> u32 {
@@ -382,6 +419,17 @@ impl Closure {
             function: Function::decode(data),
         }
     }
+
+    /// Reconstructs the Rust trait object type of this closure, like
+    /// `dyn FnMut(u32) -> String`.
+    pub fn rust_type(&self) -> String {
+        format!(
+            "dyn {}({}){}",
+            if self.mutable { "FnMut" } else { "Fn" },
+            self.function.rust_arguments(),
+            self.function.rust_return(),
+        )
+    }
 }
 
 impl Function {
@@ -396,6 +444,21 @@ impl Function {
             ret: Descriptor::_decode(data, false),
         }
     }
+
+    fn rust_arguments(&self) -> String {
+        self.arguments
+            .iter()
+            .map(|arg| arg.rust_type())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn rust_return(&self) -> String {
+        match self.ret {
+            Descriptor::Unit => String::new(),
+            ref ret => format!(" -> {}", ret.rust_type()),
+        }
+    }
 }
 
 impl VectorKind {
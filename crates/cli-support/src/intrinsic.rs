@@ -62,11 +62,26 @@ fn ref_string() -> Descriptor {
     Descriptor::Ref(Box::new(Descriptor::String))
 }
 
+fn ref_slice_u8() -> Descriptor {
+    Descriptor::Ref(Box::new(Descriptor::Slice(Box::new(Descriptor::U8))))
+}
+
+fn ref_mut_slice_u8() -> Descriptor {
+    Descriptor::RefMut(Box::new(Descriptor::Slice(Box::new(Descriptor::U8))))
+}
+
+fn ref_mut_slice_u64() -> Descriptor {
+    Descriptor::RefMut(Box::new(Descriptor::Slice(Box::new(Descriptor::U64))))
+}
+
 intrinsics! {
     pub enum Intrinsic {
         #[symbol = "__wbindgen_jsval_eq"]
         #[signature = fn(ref_anyref(), ref_anyref()) -> Boolean]
         JsvalEq,
+        #[symbol = "__wbindgen_jsval_loose_eq"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Boolean]
+        JsvalLooseEq,
         #[symbol = "__wbindgen_is_function"]
         #[signature = fn(ref_anyref()) -> Boolean]
         IsFunction,
@@ -85,6 +100,66 @@ intrinsics! {
         #[symbol = "__wbindgen_is_string"]
         #[signature = fn(ref_anyref()) -> Boolean]
         IsString,
+        #[symbol = "__wbindgen_is_bigint"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsBigint,
+        #[symbol = "__wbindgen_is_array"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsArray,
+        #[symbol = "__wbindgen_is_map"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsMap,
+        #[symbol = "__wbindgen_is_uint8_array"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsUint8Array,
+        #[symbol = "__wbindgen_is_typed_array"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsTypedArray,
+        #[symbol = "__wbindgen_is_falsy"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        IsFalsy,
+        #[symbol = "__wbindgen_typeof"]
+        #[signature = fn(ref_anyref()) -> Anyref]
+        Typeof,
+        #[symbol = "__wbindgen_in"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Boolean]
+        In,
+        #[symbol = "__wbindgen_instanceof"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Boolean]
+        Instanceof,
+        #[symbol = "__wbindgen_add"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Add,
+        #[symbol = "__wbindgen_sub"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Sub,
+        #[symbol = "__wbindgen_mul"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Mul,
+        #[symbol = "__wbindgen_div"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Div,
+        #[symbol = "__wbindgen_rem"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Rem,
+        #[symbol = "__wbindgen_pow"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Pow,
+        #[symbol = "__wbindgen_bit_and"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        BitAnd,
+        #[symbol = "__wbindgen_bit_or"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        BitOr,
+        #[symbol = "__wbindgen_bit_xor"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        BitXor,
+        #[symbol = "__wbindgen_shl"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Shl,
+        #[symbol = "__wbindgen_shr"]
+        #[signature = fn(ref_anyref(), ref_anyref()) -> Anyref]
+        Shr,
         #[symbol = "__wbindgen_object_clone_ref"]
         #[signature = fn(ref_anyref()) -> Anyref]
         ObjectCloneRef,
@@ -97,6 +172,21 @@ intrinsics! {
         #[symbol = "__wbindgen_cb_forget"]
         #[signature = fn(Anyref) -> Unit]
         CallbackForget,
+        #[symbol = "__wbindgen_cb_set_drop_policy"]
+        #[signature = fn(ref_anyref(), U32) -> Unit]
+        CallbackSetDropPolicy,
+        #[symbol = "__wbindgen_cb_is_alive"]
+        #[signature = fn(ref_anyref()) -> Boolean]
+        CallbackIsAlive,
+        #[symbol = "__wbindgen_iterator_new"]
+        #[signature = fn(ref_anyref(), ref_anyref(), Boolean) -> Anyref]
+        IteratorNew,
+        #[symbol = "__wbindgen_bigint_from_i128"]
+        #[signature = fn(U64, I64) -> Anyref]
+        BigintFromI128,
+        #[symbol = "__wbindgen_bigint_from_u128"]
+        #[signature = fn(U64, U64) -> Anyref]
+        BigintFromU128,
         #[symbol = "__wbindgen_number_new"]
         #[signature = fn(F64) -> Anyref]
         NumberNew,
@@ -115,6 +205,9 @@ intrinsics! {
         #[symbol = "__wbindgen_string_get"]
         #[signature = fn(ref_anyref(), I32) -> I32]
         StringGet,
+        #[symbol = "__wbindgen_bigint_get_as_i128"]
+        #[signature = fn(ref_anyref(), Boolean, ref_mut_slice_u64()) -> Boolean]
+        BigintGetAsI128,
         #[symbol = "__wbindgen_boolean_get"]
         #[signature = fn(ref_anyref()) -> I32]
         BooleanGet,
@@ -142,9 +235,45 @@ intrinsics! {
         #[symbol = "__wbindgen_json_serialize"]
         #[signature = fn(ref_anyref()) -> String]
         JsonSerialize,
+        #[symbol = "__wbindgen_array_new"]
+        #[signature = fn() -> Anyref]
+        ArrayNew,
+        #[symbol = "__wbindgen_array_push"]
+        #[signature = fn(ref_anyref(), Anyref) -> Unit]
+        ArrayPush,
+        #[symbol = "__wbindgen_array_get"]
+        #[signature = fn(ref_anyref(), U32) -> Anyref]
+        ArrayGet,
+        #[symbol = "__wbindgen_length"]
+        #[signature = fn(ref_anyref()) -> U32]
+        Length,
+        #[symbol = "__wbindgen_object_new"]
+        #[signature = fn() -> Anyref]
+        ObjectNew,
+        #[symbol = "__wbindgen_object_set"]
+        #[signature = fn(ref_anyref(), Anyref, Anyref) -> Unit]
+        ObjectSet,
+        #[symbol = "__wbindgen_map_new"]
+        #[signature = fn() -> Anyref]
+        MapNew,
+        #[symbol = "__wbindgen_map_set"]
+        #[signature = fn(ref_anyref(), Anyref, Anyref) -> Unit]
+        MapSet,
+        #[symbol = "__wbindgen_entries"]
+        #[signature = fn(ref_anyref()) -> Anyref]
+        Entries,
+        #[symbol = "__wbindgen_uint8_array_new"]
+        #[signature = fn(ref_slice_u8()) -> Anyref]
+        Uint8ArrayNew,
+        #[symbol = "__wbindgen_uint8_array_copy_to"]
+        #[signature = fn(ref_anyref(), ref_mut_slice_u8()) -> Unit]
+        Uint8ArrayCopyTo,
         #[symbol = "__wbindgen_anyref_heap_live_count"]
         #[signature = fn() -> I32]
         AnyrefHeapLiveCount,
+        #[symbol = "__wbindgen_heap_snapshot"]
+        #[signature = fn() -> Anyref]
+        HeapSnapshot,
         #[symbol = "__wbindgen_init_nyref_table"]
         #[signature = fn() -> Unit]
         InitAnyrefTable,
@@ -328,7 +328,7 @@ impl<'a, 'b> Js2Rust<'a, 'b> {
                 };
                 self.cx.expose_uint32_memory();
                 self.js_arguments
-                    .push(JsArgument::optional(name.clone(), "BigInt".to_string()));
+                    .push(JsArgument::optional(name.clone(), "bigint".to_string()));
                 self.prelude(&format!(
                     "
                         {f}[0] = isLikeNone({name}) ? BigInt(0) : {name};
@@ -435,7 +435,7 @@ impl<'a, 'b> Js2Rust<'a, 'b> {
             };
             self.cx.expose_uint32_memory();
             self.js_arguments
-                .push(JsArgument::required(name.clone(), "BigInt".to_string()));
+                .push(JsArgument::required(name.clone(), "bigint".to_string()));
             self.prelude(&format!(
                 "
                  {f}[0] = {name};
@@ -505,7 +505,7 @@ impl<'a, 'b> Js2Rust<'a, 'b> {
                     if self.cx.config.weak_refs {
                         self.ret_expr.push_str(&format!(
                             "\
-                             {}FinalizationGroup.register(this, this.ptr, this.ptr);
+                             {}Finalization.register(this, this.ptr, this.ptr);
                              ",
                             name
                         ));
@@ -624,7 +624,7 @@ impl<'a, 'b> Js2Rust<'a, 'b> {
             }
 
             if let Some(signed) = ty.get_64() {
-                self.ret_ty = "BigInt | undefined".to_string();
+                self.ret_ty = "bigint | undefined".to_string();
                 self.cx.expose_global_argument_ptr()?;
                 let f = if signed {
                     self.cx.expose_int64_memory();
@@ -725,7 +725,7 @@ impl<'a, 'b> Js2Rust<'a, 'b> {
         }
 
         if let Some(signed) = ty.get_64() {
-            self.ret_ty = "BigInt".to_string();
+            self.ret_ty = "bigint".to_string();
             self.cx.expose_global_argument_ptr()?;
             let f = if signed {
                 self.cx.expose_int64_memory();
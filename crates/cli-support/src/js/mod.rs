@@ -55,8 +55,11 @@ pub struct ExportedClass {
     typescript: String,
     has_constructor: bool,
     wrap_needed: bool,
+    unwrap_needed: bool,
     /// Map from field name to type as a string plus whether it has a setter
     typescript_fields: HashMap<String, (String, bool)>,
+    /// Same as `typescript_fields`, but for static properties of the class
+    typescript_static_fields: HashMap<String, (String, bool)>,
 }
 
 const INITIAL_HEAP_VALUES: &[&str] = &["undefined", "null", "true", "false"];
@@ -277,10 +280,20 @@ impl<'a> Context<'a> {
             | OutputMode::Node {
                 experimental_modules: true,
             } => {
-                imports.push_str(&format!("import * as wasm from './{}_bg';\n", module_name));
+                // Node's native ES module support doesn't search for file
+                // extensions, so point it at the `.mjs` files we generate.
+                let (bg_extension, extension) = if self.config.mode.nodejs_experimental_modules() {
+                    (".mjs", "mjs")
+                } else {
+                    ("", "js")
+                };
+                imports.push_str(&format!(
+                    "import * as wasm from './{}_bg{}';\n",
+                    module_name, bg_extension
+                ));
                 for (id, js) in sorted_iter(&self.wasm_import_definitions) {
                     let import = self.module.imports.get_mut(*id);
-                    import.module = format!("./{}.js", module_name);
+                    import.module = format!("./{}.{}", module_name, extension);
                     footer.push_str("\nexport const ");
                     footer.push_str(&import.name);
                     footer.push_str(" = ");
@@ -577,7 +590,29 @@ impl<'a> Context<'a> {
                 ",
                 name,
                 if self.config.weak_refs {
-                    format!("{}FinalizationGroup.register(obj, obj.ptr, obj.ptr);", name)
+                    format!("{}Finalization.register(obj, obj.ptr, obj.ptr);", name)
+                } else {
+                    String::new()
+                },
+            ));
+        }
+
+        if class.unwrap_needed {
+            dst.push_str(&format!(
+                "
+                static __unwrap(obj) {{
+                    if (!(obj instanceof {})) {{
+                        return 0;
+                    }}
+                    const ptr = obj.ptr;
+                    obj.ptr = 0;
+                    {}
+                    return ptr;
+                }}
+                ",
+                name,
+                if self.config.weak_refs {
+                    format!("{}Finalization.unregister(ptr);", name)
                 } else {
                     String::new()
                 },
@@ -587,11 +622,7 @@ impl<'a> Context<'a> {
         if self.config.weak_refs {
             self.global(&format!(
                 "
-                const {}FinalizationGroup = new FinalizationGroup((items) => {{
-                    for (const ptr of items) {{
-                        wasm.{}(ptr);
-                    }}
-                }});
+                const {}Finalization = new FinalizationRegistry(ptr => wasm.{}(ptr));
                 ",
                 name,
                 wasm_bindgen_shared::free_function(&name),
@@ -608,7 +639,7 @@ impl<'a> Context<'a> {
             }}
             ",
             if self.config.weak_refs {
-                format!("{}FinalizationGroup.unregister(ptr);", name)
+                format!("{}Finalization.unregister(ptr);", name)
             } else {
                 String::new()
             },
@@ -618,11 +649,13 @@ impl<'a> Context<'a> {
         dst.push_str(&class.contents);
         ts_dst.push_str(&class.typescript);
 
-        let mut fields = class.typescript_fields.keys().collect::<Vec<_>>();
-        fields.sort(); // make sure we have deterministic output
-        for name in fields {
-            let (ty, has_setter) = &class.typescript_fields[name];
+        let static_fields = sorted_iter(&class.typescript_static_fields).map(|f| (f, true));
+        let fields = sorted_iter(&class.typescript_fields).map(|f| (f, false));
+        for ((name, (ty, has_setter)), is_static) in static_fields.chain(fields) {
             ts_dst.push_str("  ");
+            if is_static {
+                ts_dst.push_str("static ");
+            }
             if !has_setter {
                 ts_dst.push_str("readonly ");
             }
@@ -687,15 +720,23 @@ impl<'a> Context<'a> {
         //
         // Otherwise the free operation here is pretty simple, just appending to
         // the linked list of heap slots that are free.
+        let clear_site = if self.config.heap_snapshot {
+            self.expose_heap_sites();
+            "heap_sites[idx] = undefined;"
+        } else {
+            ""
+        };
         self.global(&format!(
             "
             function dropObject(idx) {{
                 if (idx < {}) return;
                 heap[idx] = heap_next;
                 heap_next = idx;
+                {}
             }}
             ",
             INITIAL_HEAP_OFFSET + INITIAL_HEAP_VALUES.len(),
+            clear_site,
         ));
     }
 
@@ -709,6 +750,16 @@ impl<'a> Context<'a> {
         self.global(&format!("heap.push({});", INITIAL_HEAP_VALUES.join(", ")));
     }
 
+    fn expose_heap_sites(&mut self) {
+        if !self.should_write_global("heap_sites") {
+            return;
+        }
+        // With `--heap-snapshot` we record the JS stack, which includes the
+        // wasm functions on it, that allocated each slot of the heap for
+        // `wasm_bindgen::heap_snapshot`.
+        self.global("const heap_sites = [];");
+    }
+
     fn expose_global_heap_next(&mut self) {
         if !self.should_write_global("heap_next") {
             return;
@@ -1386,15 +1437,22 @@ impl<'a> Context<'a> {
         }
         self.expose_global_heap();
         self.expose_global_heap_next();
-        let set_heap_next = if self.config.debug {
-            String::from(
+        let mut set_heap_next = String::new();
+        if self.config.debug {
+            set_heap_next.push_str(
                 "
                 if (typeof(heap_next) !== 'number') throw new Error('corrupt heap');
                 ",
-            )
-        } else {
-            String::new()
-        };
+            );
+        }
+        if self.config.heap_snapshot {
+            self.expose_heap_sites();
+            set_heap_next.push_str(
+                "
+                heap_sites[idx] = new Error().stack;
+                ",
+            );
+        }
 
         // Allocating a slot on the heap first goes through the linked list
         // (starting at `heap_next`). Once that linked list is exhausted we'll
@@ -1656,6 +1714,10 @@ impl<'a> Context<'a> {
         require_class(&mut self.exported_classes, name).wrap_needed = true;
     }
 
+    fn require_class_unwrap(&mut self, name: &str) {
+        require_class(&mut self.exported_classes, name).unwrap_needed = true;
+    }
+
     fn import_name(&mut self, import: &JsImport) -> Result<String, Error> {
         if let Some(name) = self.imported_names.get(&import.name) {
             let mut name = name.clone();
@@ -1885,11 +1947,15 @@ impl<'a> Context<'a> {
             }
             AuxExportKind::Getter { class, field: name }
             | AuxExportKind::Setter { class, field: name }
+            | AuxExportKind::StaticGetter { class, field: name }
+            | AuxExportKind::StaticSetter { class, field: name }
             | AuxExportKind::StaticFunction { class, name }
             | AuxExportKind::Method { class, name, .. } => {
                 let mut j2r = Js2Rust::new(name, self);
                 match export.kind {
-                    AuxExportKind::StaticFunction { .. } => {}
+                    AuxExportKind::StaticFunction { .. }
+                    | AuxExportKind::StaticGetter { .. }
+                    | AuxExportKind::StaticSetter { .. } => {}
                     AuxExportKind::Method { consumed: true, .. } => {
                         j2r.method(true);
                     }
@@ -1912,6 +1978,16 @@ impl<'a> Context<'a> {
                         let exported = require_class(&mut self.exported_classes, class);
                         exported.push_setter(&docs, name, &js, &arg_ty);
                     }
+                    AuxExportKind::StaticGetter { .. } => {
+                        let ret_ty = j2r.ret_ty.clone();
+                        let exported = require_class(&mut self.exported_classes, class);
+                        exported.push_static_getter(&docs, name, &js, &ret_ty);
+                    }
+                    AuxExportKind::StaticSetter { .. } => {
+                        let arg_ty = &j2r.js_arguments[0].type_.clone();
+                        let exported = require_class(&mut self.exported_classes, class);
+                        exported.push_static_setter(&docs, name, &js, &arg_ty);
+                    }
                     AuxExportKind::StaticFunction { .. } => {
                         let exported = require_class(&mut self.exported_classes, class);
                         exported.push(&docs, name, "static ", &js, &ts);
@@ -1922,6 +1998,25 @@ impl<'a> Context<'a> {
                     }
                 }
             }
+            AuxExportKind::Hook {
+                class,
+                hook,
+                consumed,
+            } => {
+                let key = hook.js_key();
+                let mut j2r = Js2Rust::new(key, self);
+                j2r.method(*consumed);
+                let (js, ts, raw_docs) = j2r
+                    .process(&descriptor, &export.arg_names)?
+                    .finish("", &format!("wasm.{}", wasm_name));
+                let docs = format_doc_comments(&export.comments, Some(raw_docs));
+                let exported = require_class(&mut self.exported_classes, class);
+                if hook.has_typescript() {
+                    exported.push(&docs, key, "", &js, &ts);
+                } else {
+                    exported.push_js(&docs, key, "", &js);
+                }
+            }
         }
         Ok(())
     }
@@ -2034,6 +2129,133 @@ impl<'a> Context<'a> {
         Ok(())
     }
 
+    fn expose_closure_finalization(&mut self) {
+        if !self.should_write_global("closure_finalization") {
+            return;
+        }
+
+        // Closures created with weak references enabled are registered here
+        // holding a reference on their Rust environment, just like a
+        // `Closure` in Rust does. Dropping the `Closure` unregisters the JS
+        // function, but if ownership was handed off to JS with
+        // `Closure::into_js_value` then the environment is destroyed once the
+        // function is garbage collected.
+        self.expose_closure_destroyed();
+        self.global(
+            "
+            const CLOSURE_DTORS = new FinalizationRegistry(state => {
+                if (--state.cb.cnt === 0) {
+                    state.d(state.cb.a, state.b);
+                    closureDestroyed(state.cb);
+                }
+            });
+            ",
+        );
+    }
+
+    fn expose_closure_destroyed(&mut self) {
+        if !self.should_write_global("closure_destroyed") {
+            return;
+        }
+
+        // Called whenever the Rust environment of a closure has been
+        // destroyed, which runs the JS drop hook set through the `ondrop`
+        // property of the function. Exceptions thrown by the hook can't be
+        // propagated as this may be in the middle of a destructor in Rust, so
+        // they're just reported.
+        self.global(
+            "
+            function closureDestroyed(state) {
+                state.a = 0;
+                if (typeof state.ondrop === 'function') {
+                    try { state.ondrop(); } catch (e) { console.error(e); }
+                }
+            }
+            ",
+        );
+    }
+
+    fn expose_closure_dropped(&mut self) {
+        if !self.should_write_global("closure_dropped") {
+            return;
+        }
+
+        // The drop policies here must be kept in sync with
+        // `wasm_bindgen::closure::DropPolicy`.
+        self.global(
+            "
+            function closureDropped(state, ty) {
+                const msg = `closure of type \\`${ty}\\` invoked after being dropped`;
+                if (state.dropPolicy === 1) return;
+                if (state.dropPolicy === 2) {
+                    console.warn(msg);
+                    return;
+                }
+                const e = new Error(msg);
+                e.name = 'DroppedClosureError';
+                throw e;
+            }
+            ",
+        );
+    }
+
+    fn expose_make_iterator(&mut self) {
+        if !self.should_write_global("make_iterator") {
+            return;
+        }
+
+        // See `wasm_bindgen::iter::__make_iterator` for the protocol of
+        // `advance` and `take`.
+        self.global(
+            "
+            function makeIterator(advance, take, isAsync) {
+                let finished = false;
+                const next = more => {
+                    if (more) return { value: take(), done: false };
+                    finished = true;
+                    return { value: undefined, done: true };
+                };
+
+                if (!isAsync) {
+                    return {
+                        next: () => next(!finished && advance(false)),
+                        return: value => {
+                            if (!finished) {
+                                finished = true;
+                                advance(true);
+                            }
+                            return { value, done: true };
+                        },
+                        [Symbol.iterator]() { return this; },
+                    };
+                }
+
+                let last = Promise.resolve();
+                const enqueue = f => {
+                    const result = last.then(f);
+                    last = result.catch(() => {});
+                    return result;
+                };
+                return {
+                    next: () => enqueue(() => {
+                        if (finished) return next(false);
+                        return Promise.resolve(advance(false)).then(next, e => {
+                            finished = true;
+                            throw e;
+                        });
+                    }),
+                    return: value => enqueue(() => {
+                        if (finished) return { value, done: true };
+                        finished = true;
+                        return Promise.resolve(advance(true)).then(() => ({ value, done: true }));
+                    }),
+                    [Symbol.asyncIterator]() { return this; },
+                };
+            }
+            ",
+        );
+    }
+
     fn expose_debug_string(&mut self) {
         if !self.should_write_global("debug_string") {
             return;
@@ -2157,11 +2379,7 @@ fn require_class<'a>(
 
 impl ExportedClass {
     fn push(&mut self, docs: &str, function_name: &str, function_prefix: &str, js: &str, ts: &str) {
-        self.contents.push_str(docs);
-        self.contents.push_str(function_prefix);
-        self.contents.push_str(function_name);
-        self.contents.push_str(js);
-        self.contents.push_str("\n");
+        self.push_js(docs, function_name, function_prefix, js);
         self.typescript.push_str(docs);
         self.typescript.push_str("  ");
         self.typescript.push_str(function_prefix);
@@ -2169,16 +2387,36 @@ impl ExportedClass {
         self.typescript.push_str("\n");
     }
 
+    /// Same as `push`, but without a TypeScript declaration.
+    fn push_js(&mut self, docs: &str, function_name: &str, function_prefix: &str, js: &str) {
+        self.contents.push_str(docs);
+        self.contents.push_str(function_prefix);
+        self.contents.push_str(function_name);
+        self.contents.push_str(js);
+        self.contents.push_str("\n");
+    }
+
     /// Used for adding a getter to a class, mainly to ensure that TypeScript
     /// generation is handled specially.
     fn push_getter(&mut self, docs: &str, field: &str, js: &str, ret_ty: &str) {
-        self.push_accessor(docs, field, js, "get ", ret_ty);
+        self.push_accessor(docs, field, js, "get ", ret_ty, false);
     }
 
     /// Used for adding a setter to a class, mainly to ensure that TypeScript
     /// generation is handled specially.
     fn push_setter(&mut self, docs: &str, field: &str, js: &str, ret_ty: &str) {
-        let has_setter = self.push_accessor(docs, field, js, "set ", ret_ty);
+        let has_setter = self.push_accessor(docs, field, js, "set ", ret_ty, false);
+        *has_setter = true;
+    }
+
+    /// Same as `push_getter`, but the property is on the class itself.
+    fn push_static_getter(&mut self, docs: &str, field: &str, js: &str, ret_ty: &str) {
+        self.push_accessor(docs, field, js, "static get ", ret_ty, true);
+    }
+
+    /// Same as `push_setter`, but the property is on the class itself.
+    fn push_static_setter(&mut self, docs: &str, field: &str, js: &str, ret_ty: &str) {
+        let has_setter = self.push_accessor(docs, field, js, "static set ", ret_ty, true);
         *has_setter = true;
     }
 
@@ -2189,14 +2427,19 @@ impl ExportedClass {
         js: &str,
         prefix: &str,
         ret_ty: &str,
+        is_static: bool,
     ) -> &mut bool {
         self.contents.push_str(docs);
         self.contents.push_str(prefix);
         self.contents.push_str(field);
         self.contents.push_str(js);
         self.contents.push_str("\n");
-        let (ty, has_setter) = self
-            .typescript_fields
+        let fields = if is_static {
+            &mut self.typescript_static_fields
+        } else {
+            &mut self.typescript_fields
+        };
+        let (ty, has_setter) = fields
             .entry(field.to_string())
             .or_insert_with(Default::default);
         *ty = ret_ty.to_string();
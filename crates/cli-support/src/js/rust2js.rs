@@ -751,10 +751,20 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 let (js, _ts, _js_doc) = {
                     let mut builder = Js2Rust::new("", self.cx);
 
+                    // If the `Closure` has already been dropped in Rust then
+                    // there's nothing to invoke, so instead we do whatever its
+                    // drop policy says.
+                    self.cx.expose_closure_dropped();
+                    builder.prelude(&format!(
+                        "if (this.cnt === 0) return closureDropped(this, '{}');",
+                        closure.rust_type(),
+                    ));
+
                     // First up with a closure we increment the internal reference
                     // count. This ensures that the Rust closure environment won't
                     // be deallocated while we're invoking it.
                     builder.prelude("this.cnt++;");
+                    self.cx.expose_closure_destroyed();
 
                     if closure.mutable {
                         // For mutable closures they can't be invoked recursively.
@@ -767,20 +777,24 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                             .prelude("this.a = 0;")
                             .rust_argument("a")
                             .rust_argument("b")
-                            .finally("if (--this.cnt === 0) d(a, b);")
-                            .finally("else this.a = a;");
+                            .finally("if (--this.cnt === 0) {")
+                            .finally("d(a, b);")
+                            .finally("closureDestroyed(this);")
+                            .finally("} else {")
+                            .finally("this.a = a;")
+                            .finally("}");
                     } else {
                         // For shared closures they can be invoked recursively so we
-                        // just immediately pass through `this.a`. If we end up
-                        // executing the destructor, however, we clear out the
-                        // `this.a` pointer to prevent it being used again the
-                        // future.
+                        // just immediately pass through `this.a`. Dropping the
+                        // `Closure` in the meantime clears out `this.a`, so we
+                        // hold on to the pointer to run the destructor with.
                         builder
-                            .rust_argument("this.a")
+                            .prelude("const a = this.a;")
+                            .rust_argument("a")
                             .rust_argument("b")
                             .finally("if (--this.cnt === 0) {")
-                            .finally("d(this.a, b);")
-                            .finally("this.a = 0;")
+                            .finally("d(a, b);")
+                            .finally("closureDestroyed(this);")
                             .finally("}");
                     }
                     builder
@@ -798,6 +812,10 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                         cb.cnt = 1;
                         let real = cb.bind(cb);
                         real.original = cb;
+                        Object.defineProperty(real, 'ondrop', {{
+                            get() {{ return cb.ondrop; }},
+                            set(f) {{ cb.ondrop = f; }},
+                        }});
                     ",
                     closure.shim_idx,
                     closure.dtor_idx,
@@ -806,6 +824,10 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                     &self.js_arguments[0],
                 );
                 self.prelude(&body);
+                if self.cx.config.weak_refs {
+                    self.cx.expose_closure_finalization();
+                    self.prelude("CLOSURE_DTORS.register(real, { cb, d, b }, cb);");
+                }
                 "real".to_string()
             }
 
@@ -913,6 +935,14 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 format!("{}.__wrap({})", class, self.js_arguments[0])
             }
 
+            AuxImport::UnwrapExportedClass(class) => {
+                assert!(self.style == Style::Function);
+                assert!(!variadic);
+                assert_eq!(self.js_arguments.len(), 1);
+                self.cx.require_class_unwrap(class);
+                format!("{}.__unwrap({})", class, self.js_arguments[0])
+            }
+
             AuxImport::Intrinsic(intrinsic) => {
                 assert!(self.style == Style::Function);
                 assert!(!variadic);
@@ -1011,6 +1041,11 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 format!("{} === {}", self.js_arguments[0], self.js_arguments[1])
             }
 
+            Intrinsic::JsvalLooseEq => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!("{} == {}", self.js_arguments[0], self.js_arguments[1])
+            }
+
             Intrinsic::IsFunction => {
                 assert_eq!(self.js_arguments.len(), 1);
                 format!("typeof({}) === 'function'", self.js_arguments[0])
@@ -1042,6 +1077,84 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 format!("typeof({}) === 'string'", self.js_arguments[0])
             }
 
+            Intrinsic::IsBigint => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("typeof({}) === 'bigint'", self.js_arguments[0])
+            }
+
+            Intrinsic::IsArray => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("Array.isArray({})", self.js_arguments[0])
+            }
+
+            Intrinsic::IsMap => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("{} instanceof Map", self.js_arguments[0])
+            }
+
+            Intrinsic::IsUint8Array => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("{} instanceof Uint8Array", self.js_arguments[0])
+            }
+
+            Intrinsic::IsTypedArray => {
+                assert_eq!(self.js_arguments.len(), 1);
+                self.prelude(&format!("const v = {};", self.js_arguments[0]));
+                "ArrayBuffer.isView(v) && !(v instanceof DataView)".to_string()
+            }
+
+            Intrinsic::IsFalsy => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("!{}", self.js_arguments[0])
+            }
+
+            Intrinsic::Typeof => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("typeof({})", self.js_arguments[0])
+            }
+
+            Intrinsic::In => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!("{} in {}", self.js_arguments[0], self.js_arguments[1])
+            }
+
+            Intrinsic::Instanceof => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!(
+                    "{} instanceof {}",
+                    self.js_arguments[0], self.js_arguments[1]
+                )
+            }
+
+            Intrinsic::Add
+            | Intrinsic::Sub
+            | Intrinsic::Mul
+            | Intrinsic::Div
+            | Intrinsic::Rem
+            | Intrinsic::Pow
+            | Intrinsic::BitAnd
+            | Intrinsic::BitOr
+            | Intrinsic::BitXor
+            | Intrinsic::Shl
+            | Intrinsic::Shr => {
+                assert_eq!(self.js_arguments.len(), 2);
+                let op = match intrinsic {
+                    Intrinsic::Add => "+",
+                    Intrinsic::Sub => "-",
+                    Intrinsic::Mul => "*",
+                    Intrinsic::Div => "/",
+                    Intrinsic::Rem => "%",
+                    Intrinsic::Pow => "**",
+                    Intrinsic::BitAnd => "&",
+                    Intrinsic::BitOr => "|",
+                    Intrinsic::BitXor => "^",
+                    Intrinsic::Shl => "<<",
+                    Intrinsic::Shr => ">>",
+                    _ => unreachable!(),
+                };
+                format!("{} {} {}", self.js_arguments[0], op, self.js_arguments[1])
+            }
+
             Intrinsic::ObjectCloneRef => {
                 assert_eq!(self.js_arguments.len(), 1);
                 self.js_arguments[0].clone()
@@ -1055,11 +1168,18 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
             Intrinsic::CallbackDrop => {
                 assert_eq!(self.js_arguments.len(), 1);
                 self.prelude(&format!("const obj = {}.original;", self.js_arguments[0]));
-                self.prelude("if (obj.cnt-- == 1) {");
+                if self.cx.config.weak_refs {
+                    self.cx.expose_closure_finalization();
+                    self.prelude("CLOSURE_DTORS.unregister(obj);");
+                }
+                // If the closure is being invoked it's destroyed once the
+                // invocation finishes, but it can't be called anymore either
+                // way.
+                self.cx.expose_closure_destroyed();
+                self.prelude("const dropped = obj.cnt-- == 1;");
                 self.prelude("obj.a = 0;");
-                self.prelude("return true;");
-                self.prelude("}");
-                "false".to_string()
+                self.prelude("if (dropped) closureDestroyed(obj);");
+                "dropped".to_string()
             }
 
             Intrinsic::CallbackForget => {
@@ -1067,6 +1187,39 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 self.js_arguments[0].clone()
             }
 
+            Intrinsic::CallbackSetDropPolicy => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!(
+                    "{}.original.dropPolicy = {}",
+                    self.js_arguments[0], self.js_arguments[1]
+                )
+            }
+
+            Intrinsic::CallbackIsAlive => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!(
+                    "typeof {0} === 'function' && {0}.original !== undefined && {0}.original.a !== 0",
+                    self.js_arguments[0]
+                )
+            }
+
+            Intrinsic::IteratorNew => {
+                assert_eq!(self.js_arguments.len(), 3);
+                self.cx.expose_make_iterator();
+                format!(
+                    "makeIterator({}, {}, {})",
+                    self.js_arguments[0], self.js_arguments[1], self.js_arguments[2],
+                )
+            }
+
+            Intrinsic::BigintFromI128 | Intrinsic::BigintFromU128 => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!(
+                    "{} << BigInt(64) | {}",
+                    self.js_arguments[1], self.js_arguments[0]
+                )
+            }
+
             Intrinsic::NumberNew => {
                 assert_eq!(self.js_arguments.len(), 1);
                 self.js_arguments[0].clone()
@@ -1110,6 +1263,26 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 "ptr".to_string()
             }
 
+            Intrinsic::BigintGetAsI128 => {
+                assert_eq!(self.js_arguments.len(), 3);
+                self.prelude(&format!("const v = {};", self.js_arguments[0]));
+                self.prelude("if (typeof(v) !== 'bigint') return false;");
+                self.prelude(&format!(
+                    "const n = {} ? BigInt.asIntN(128, v) : BigInt.asUintN(128, v);",
+                    self.js_arguments[1],
+                ));
+                self.prelude("if (n !== v) return false;");
+                self.prelude(&format!(
+                    "{}[0] = BigInt.asUintN(64, v);",
+                    self.js_arguments[2]
+                ));
+                self.prelude(&format!(
+                    "{}[1] = BigInt.asUintN(64, v >> BigInt(64));",
+                    self.js_arguments[2]
+                ));
+                "true".to_string()
+            }
+
             Intrinsic::BooleanGet => {
                 assert_eq!(self.js_arguments.len(), 1);
                 self.prelude(&format!("const v = {};", self.js_arguments[0]));
@@ -1163,6 +1336,68 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 format!("JSON.stringify({})", self.js_arguments[0])
             }
 
+            Intrinsic::ArrayNew => {
+                assert_eq!(self.js_arguments.len(), 0);
+                "[]".to_string()
+            }
+
+            Intrinsic::ArrayPush => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!("{}.push({})", self.js_arguments[0], self.js_arguments[1])
+            }
+
+            Intrinsic::ArrayGet => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!("{}[{}]", self.js_arguments[0], self.js_arguments[1])
+            }
+
+            Intrinsic::Length => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("{}.length", self.js_arguments[0])
+            }
+
+            Intrinsic::ObjectNew => {
+                assert_eq!(self.js_arguments.len(), 0);
+                "{}".to_string()
+            }
+
+            Intrinsic::ObjectSet => {
+                assert_eq!(self.js_arguments.len(), 3);
+                format!(
+                    "{}[{}] = {}",
+                    self.js_arguments[0], self.js_arguments[1], self.js_arguments[2],
+                )
+            }
+
+            Intrinsic::MapNew => {
+                assert_eq!(self.js_arguments.len(), 0);
+                "new Map()".to_string()
+            }
+
+            Intrinsic::MapSet => {
+                assert_eq!(self.js_arguments.len(), 3);
+                format!(
+                    "{}.set({}, {})",
+                    self.js_arguments[0], self.js_arguments[1], self.js_arguments[2],
+                )
+            }
+
+            Intrinsic::Entries => {
+                assert_eq!(self.js_arguments.len(), 1);
+                self.prelude(&format!("const obj = {};", self.js_arguments[0]));
+                "obj instanceof Map ? Array.from(obj) : Object.entries(obj)".to_string()
+            }
+
+            Intrinsic::Uint8ArrayNew => {
+                assert_eq!(self.js_arguments.len(), 1);
+                format!("{}.slice()", self.js_arguments[0])
+            }
+
+            Intrinsic::Uint8ArrayCopyTo => {
+                assert_eq!(self.js_arguments.len(), 2);
+                format!("{}.set({})", self.js_arguments[1], self.js_arguments[0])
+            }
+
             Intrinsic::AnyrefHeapLiveCount => {
                 assert_eq!(self.js_arguments.len(), 0);
                 if self.cx.config.anyref {
@@ -1197,6 +1432,35 @@ impl<'a, 'b> Rust2Js<'a, 'b> {
                 }
             }
 
+            Intrinsic::HeapSnapshot => {
+                assert_eq!(self.js_arguments.len(), 0);
+                // Allocation sites are only recorded with `--heap-snapshot`,
+                // and there's no JS heap to inspect with anyref.
+                if self.cx.config.anyref || !self.cx.config.heap_snapshot {
+                    "undefined".to_string()
+                } else {
+                    self.cx.expose_heap_sites();
+                    // Stacks are recorded as is when allocating, so here we
+                    // strip the message and the frame of `addHeapObject`
+                    // itself before grouping them.
+                    self.prelude(
+                        "
+                            const counts = new Map();
+                            for (const stack of heap_sites) {
+                                if (stack === undefined) continue;
+                                const site = stack
+                                    .split('\\n')
+                                    .map(line => line.trim())
+                                    .filter(line => line && line !== 'Error' && !line.includes('addHeapObject'))
+                                    .join('\\n');
+                                counts.set(site, (counts.get(site) || 0) + 1);
+                            }
+                        ",
+                    );
+                    "Array.from(counts).sort((a, b) => b[1] - a[1])".to_string()
+                }
+            }
+
             Intrinsic::InitAnyrefTable => {
                 self.cx.expose_anyref_table();
                 String::from(
@@ -32,6 +32,9 @@ pub struct Bindgen {
     // Experimental support for weakrefs, an upcoming ECMAScript feature.
     // Currently only enable-able through an env var.
     weak_refs: bool,
+    // Record the stack trace which allocated each JS heap slot for
+    // `wasm_bindgen::heap_snapshot`.
+    heap_snapshot: bool,
     // Experimental support for the wasm threads proposal, transforms the wasm
     // module to be "ready to be instantiated on any thread"
     threads: Option<wasm_bindgen_threads_xform::Config>,
@@ -87,6 +90,7 @@ impl Bindgen {
             remove_producers_section: false,
             emit_start: true,
             weak_refs: env::var("WASM_BINDGEN_WEAKREF").is_ok(),
+            heap_snapshot: env::var("WASM_BINDGEN_HEAP_SNAPSHOT").is_ok(),
             threads: threads_config(),
             anyref: env::var("WASM_BINDGEN_ANYREF").is_ok(),
             encode_into: EncodeInto::Test,
@@ -234,6 +238,26 @@ impl Bindgen {
         self
     }
 
+    /// Whether to use JS weak references (`FinalizationRegistry`) to free
+    /// Rust memory owned by JS objects once they're garbage collected.
+    ///
+    /// Defaults to whether the `WASM_BINDGEN_WEAKREF` environment variable is
+    /// set.
+    pub fn weak_refs(&mut self, enable: bool) -> &mut Bindgen {
+        self.weak_refs = enable;
+        self
+    }
+
+    /// Whether to record the stack trace which allocated each `JsValue`, so
+    /// that `wasm_bindgen::heap_snapshot` can list the live ones.
+    ///
+    /// Defaults to whether the `WASM_BINDGEN_HEAP_SNAPSHOT` environment
+    /// variable is set.
+    pub fn heap_snapshot(&mut self, enable: bool) -> &mut Bindgen {
+        self.heap_snapshot = enable;
+        self
+    }
+
     pub fn generate<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
         self._generate(path.as_ref())
     }
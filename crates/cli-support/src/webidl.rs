@@ -174,6 +174,14 @@ pub enum AuxExportKind {
     /// This is a free function (ish) but scoped inside of a class name.
     StaticFunction { class: String, name: String },
 
+    /// A getter for a property of the class itself rather than of its
+    /// instances, such as an exported associated constant.
+    StaticGetter { class: String, field: String },
+
+    /// A setter for a property of the class itself rather than of its
+    /// instances.
+    StaticSetter { class: String, field: String },
+
     /// This is a member function of a class where the first parameter is the
     /// implicit integer stored in the class instance.
     Method {
@@ -183,6 +191,48 @@ pub enum AuxExportKind {
         /// clear the internal pointer in JS automatically.
         consumed: bool,
     },
+
+    /// Same as `Method`, except that the method is installed under a
+    /// well-known name or symbol, like `toString` or `Symbol.iterator`.
+    Hook {
+        class: String,
+        hook: AuxClassHook,
+        consumed: bool,
+    },
+}
+
+/// All well-known methods that an exported method can be installed as.
+#[derive(Debug, Copy, Clone)]
+pub enum AuxClassHook {
+    Iterator,
+    AsyncIterator,
+    ToPrimitive,
+    ValueOf,
+    ToString,
+    Inspect,
+}
+
+impl AuxClassHook {
+    /// The property key of this method in a JS class body.
+    pub fn js_key(&self) -> &'static str {
+        match self {
+            AuxClassHook::Iterator => "[Symbol.iterator]",
+            AuxClassHook::AsyncIterator => "[Symbol.asyncIterator]",
+            AuxClassHook::ToPrimitive => "[Symbol.toPrimitive]",
+            AuxClassHook::ValueOf => "valueOf",
+            AuxClassHook::ToString => "toString",
+            AuxClassHook::Inspect => "[Symbol.for('nodejs.util.inspect.custom')]",
+        }
+    }
+
+    /// Whether this method can be declared in TypeScript, which only allows
+    /// computed property names of unique symbols in class declarations.
+    pub fn has_typescript(&self) -> bool {
+        match self {
+            AuxClassHook::Inspect => false,
+            _ => true,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -337,6 +387,11 @@ pub enum AuxImport {
     /// requiring an intrinsic here to do so.
     WrapInExportedClass(String),
 
+    /// This import is a generated shim which takes the pointer out of a JS
+    /// object if it's an instance of the exported class named here, returning
+    /// 0 otherwise.
+    UnwrapExportedClass(String),
+
     /// This is an intrinsic function expected to be implemented with a JS glue
     /// shim. Each intrinsic has its own expected signature and implementation.
     Intrinsic(Intrinsic),
@@ -595,6 +650,18 @@ impl<'a> Context<'a> {
                 match export.method_kind {
                     decode::MethodKind::Constructor => AuxExportKind::Constructor(class),
                     decode::MethodKind::Operation(op) => match op.kind {
+                        decode::OperationKind::Getter(f) if op.is_static => {
+                            AuxExportKind::StaticGetter {
+                                class,
+                                field: f.to_string(),
+                            }
+                        }
+                        decode::OperationKind::Setter(f) if op.is_static => {
+                            AuxExportKind::StaticSetter {
+                                class,
+                                field: f.to_string(),
+                            }
+                        }
                         decode::OperationKind::Getter(f) => {
                             descriptor.arguments.insert(0, Descriptor::I32);
                             AuxExportKind::Getter {
@@ -609,6 +676,21 @@ impl<'a> Context<'a> {
                                 field: f.to_string(),
                             }
                         }
+                        decode::OperationKind::Hook(hook) => {
+                            descriptor.arguments.insert(0, Descriptor::I32);
+                            AuxExportKind::Hook {
+                                class,
+                                hook: match hook {
+                                    decode::ClassHook::Iterator => AuxClassHook::Iterator,
+                                    decode::ClassHook::AsyncIterator => AuxClassHook::AsyncIterator,
+                                    decode::ClassHook::ToPrimitive => AuxClassHook::ToPrimitive,
+                                    decode::ClassHook::ValueOf => AuxClassHook::ValueOf,
+                                    decode::ClassHook::ToString => AuxClassHook::ToString,
+                                    decode::ClassHook::Inspect => AuxClassHook::Inspect,
+                                },
+                                consumed: export.consumed,
+                            }
+                        }
                         _ if op.is_static => AuxExportKind::StaticFunction {
                             class,
                             name: export.function.name.to_string(),
@@ -853,6 +935,10 @@ impl<'a> Context<'a> {
                     Ok((AuxImport::IndexingDeleterOfObject, false))
                 }
             }
+
+            decode::OperationKind::Hook(_) => {
+                bail!("well-known methods can only be defined on exported classes")
+            }
         }
     }
 
@@ -1005,6 +1091,22 @@ impl<'a> Context<'a> {
                 .insert(*import_id, ImportBinding::Function(binding));
         }
 
+        let unwrap_fn = wasm_bindgen_shared::unwrap_function(struct_.name);
+        if let Some((import_id, _id)) = self.function_imports.get(&unwrap_fn) {
+            self.aux.import_map.insert(
+                *import_id,
+                AuxImport::UnwrapExportedClass(struct_.name.to_string()),
+            );
+            let binding = Function {
+                shim_idx: 0,
+                arguments: vec![Descriptor::Ref(Box::new(Descriptor::Anyref))],
+                ret: Descriptor::U32,
+            };
+            self.bindings
+                .imports
+                .insert(*import_id, ImportBinding::Function(binding));
+        }
+
         Ok(())
     }
 
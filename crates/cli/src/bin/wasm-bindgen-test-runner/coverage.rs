@@ -0,0 +1,188 @@
+//! Support for collecting code coverage of a test run.
+//!
+//! When `WASM_BINDGEN_TEST_COVERAGE` is set the wasm file emitted by
+//! `wasm-bindgen` is instrumented with a counter for every block of every
+//! function, each counter being a mutable global which is incremented whenever
+//! its block is entered. An exported function, `__wbgtest_coverage_dump`, then
+//! copies all the counters into freshly grown pages of linear memory so the JS
+//! running the tests can read them out once the tests have finished and render
+//! an lcov report.
+//!
+//! Note that the instrumentation happens *after* `wasm-bindgen` has processed
+//! the module. The interpreter used for descriptor functions assumes that all
+//! globals are the stack pointer, so instrumenting those functions beforehand
+//! would break it.
+//!
+//! The counters are mapped back to source code at the level of functions: if
+//! the wasm file being tested has DWARF debug information each function is
+//! located at the file and line it starts at, and its blocks are reported as
+//! branches of that line. Functions without line information, or all of them
+//! if there's no debug information, are reported at line 0 of the wasm file
+//! itself. Which lines *within* a function were executed isn't known, as the
+//! counters are added to blocks without knowing where their code came from.
+
+use failure::{bail, Error, ResultExt};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use walrus::ir::*;
+use walrus::{GlobalId, InitExpr, LocalFunction, Module, ValType};
+
+use crate::dwarf;
+
+const PAGE_SIZE: u32 = 1 << 16;
+
+/// The counters injected into a wasm module, described in a way that the JS
+/// rendering the report understands.
+#[derive(Serialize)]
+pub struct Coverage {
+    /// The path to write the lcov report to.
+    output: String,
+    /// The wasm file being tested, named as the source file of functions
+    /// without line information.
+    source: String,
+    /// The total number of counters in the module.
+    counters: u32,
+    /// The instrumented functions.
+    functions: Vec<Function>,
+}
+
+#[derive(Serialize)]
+struct Function {
+    name: String,
+    /// The source file this function is defined in, if known.
+    file: Option<String>,
+    /// The line this function starts at, or 0 if not known.
+    line: u64,
+    /// The index of the first counter of this function, which is that of its
+    /// entry block and hence counts how many times the function was called.
+    first: u32,
+    /// The number of counters of this function, one for each of its blocks.
+    blocks: u32,
+}
+
+/// Instruments the wasm file at `path` in place, returning a description of
+/// the counters which were added along with where the report for the tests of
+/// `source` should be written.
+pub fn instrument(path: &Path, source: &Path, output: &Path) -> Result<Coverage, Error> {
+    let wasm = fs::read(path).context("failed to read wasm file")?;
+    let mut module = Module::from_buffer(&wasm).context("failed to deserialize wasm module")?;
+
+    let memory = match module.memories.iter().next() {
+        Some(memory) => memory.id(),
+        None => bail!("cannot collect coverage of a wasm module without a memory"),
+    };
+
+    let mut globals = Vec::new();
+    let mut instrumented = Vec::new();
+    for (id, func) in module.funcs.iter_local_mut() {
+        let first = globals.len() as u32;
+        let mut entry = func.entry_block();
+        Instrument {
+            func,
+            globals: &mut module.globals,
+            counters: &mut globals,
+        }
+        .visit_block_id_mut(&mut entry);
+        instrumented.push((id, first, globals.len() as u32 - first));
+    }
+
+    // The wasm file emitted by `wasm-bindgen` doesn't have any debug
+    // information matching its code, so functions are looked up by name in
+    // the original one.
+    let original = fs::read(source).context("failed to read wasm file")?;
+    let locations = dwarf::function_locations(&original)
+        .context("failed to read line information of wasm file")?;
+    let functions = instrumented
+        .into_iter()
+        .map(|(id, first, blocks)| {
+            let name = match &module.funcs.get(id).name {
+                Some(name) => name.clone(),
+                None => format!("{:?}", id),
+            };
+            let (file, line) = match locations.get(&name) {
+                Some((file, line)) => (Some(file.to_string()), *line),
+                None => (None, 0),
+            };
+            Function {
+                name,
+                file,
+                line,
+                first,
+                blocks,
+            }
+        })
+        .collect();
+
+    // Generate `__wbgtest_coverage_dump`, which grows memory by enough pages
+    // to hold all the counters, stores them there and returns the address of
+    // the first one.
+    let counters = globals.len() as u32;
+    let pages = (counters * 4 + PAGE_SIZE - 1) / PAGE_SIZE;
+    let mut builder = walrus::FunctionBuilder::new();
+    let mut exprs = Vec::new();
+    let base = module.locals.add(ValType::I32);
+
+    let delta = builder.i32_const(pages as i32);
+    let grow = builder.memory_grow(memory, delta);
+    let shift = builder.i32_const(16);
+    let address = builder.binop(BinaryOp::I32Shl, grow, shift);
+    exprs.push(builder.local_set(base, address));
+    for (i, global) in globals.iter().enumerate() {
+        let address = builder.local_get(base);
+        let value = builder.global_get(*global);
+        let arg = MemArg {
+            align: 4,
+            offset: i as u32 * 4,
+        };
+        let kind = StoreKind::I32 { atomic: false };
+        exprs.push(builder.store(memory, kind, arg, address, value));
+    }
+    exprs.push(builder.local_get(base));
+
+    let ty = module.types.add(&[], &[ValType::I32]);
+    let dump = builder.finish(ty, Vec::new(), exprs, &mut module);
+    module.funcs.get_mut(dump).name = Some("__wbgtest_coverage_dump".to_string());
+    module.exports.add("__wbgtest_coverage_dump", dump);
+
+    let wasm = module.emit_wasm()?;
+    fs::write(path, wasm).context("failed to write instrumented wasm file")?;
+
+    Ok(Coverage {
+        output: output.to_string_lossy().into_owned(),
+        source: source.to_string_lossy().into_owned(),
+        counters,
+        functions,
+    })
+}
+
+struct Instrument<'a> {
+    func: &'a mut LocalFunction,
+    globals: &'a mut walrus::ModuleGlobals,
+    counters: &'a mut Vec<GlobalId>,
+}
+
+impl VisitorMut for Instrument<'_> {
+    fn local_function_mut(&mut self) -> &mut LocalFunction {
+        self.func
+    }
+
+    fn visit_block_mut(&mut self, e: &mut Block) {
+        // Allocate this block's counter before visiting nested blocks so the
+        // entry block of a function always gets its first counter.
+        let global = self
+            .globals
+            .add_local(ValType::I32, true, InitExpr::Value(Value::I32(0)));
+        self.counters.push(global);
+
+        e.visit_mut(self);
+
+        // Prepend `global = global + 1` to the block.
+        let builder = self.func.builder_mut();
+        let get = builder.global_get(global);
+        let one = builder.i32_const(1);
+        let add = builder.binop(BinaryOp::I32Add, get, one);
+        let set = builder.global_set(global, add);
+        e.exprs.insert(0, set);
+    }
+}
@@ -0,0 +1,185 @@
+//! Locating the functions of a wasm module in source code through the DWARF
+//! debug information which rustc emits into custom sections.
+//!
+//! Code addresses in the DWARF of a wasm module are offsets into the payload
+//! of its code section. This only holds for the module as rustc emitted it and
+//! not for the one emitted by `wasm-bindgen`, which rewrites all function
+//! bodies without updating the debug information, so the functions are
+//! located in the original module and identified by their names in the name
+//! section, which `wasm-bindgen` preserves.
+
+use failure::{bail, Error};
+use gimli::{EndianSlice, LittleEndian};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::rc::Rc;
+use wasmparser::{
+    CustomSectionKind, ImportSectionEntryType, ModuleReader, Name, Section, SectionCode,
+};
+
+type Reader<'a> = EndianSlice<'a, LittleEndian>;
+
+/// Returns the source file and line each named function of the wasm module
+/// `wasm` starts at, keyed by the function's name. The map is empty if the
+/// module has no line information.
+pub fn function_locations(wasm: &[u8]) -> Result<HashMap<String, (Rc<str>, u64)>, Error> {
+    let module = Sections::parse(wasm)?;
+    let mut locations = HashMap::new();
+    if !module.customs.contains_key(".debug_line") {
+        return Ok(locations);
+    }
+
+    let rows = line_rows(&module.customs)?;
+    for (index, name) in &module.names {
+        let body = match index
+            .checked_sub(module.imported_functions)
+            .and_then(|i| module.bodies.get(i as usize))
+        {
+            Some(body) => body,
+            None => continue,
+        };
+        // The first row of a function is usually that of the line its
+        // signature is on.
+        if let Some((_, (file, line))) = rows.range(body.0..body.1).next() {
+            locations.insert(name.clone(), (file.clone(), *line));
+        }
+    }
+    Ok(locations)
+}
+
+/// The parts of a wasm module needed to locate its functions.
+struct Sections<'a> {
+    /// The contents of all custom sections other than the name section, keyed
+    /// by their names.
+    customs: HashMap<&'a str, &'a [u8]>,
+    /// The names of functions from the name section, keyed by their indices.
+    names: Vec<(u32, String)>,
+    /// The number of imported functions, which come before the functions
+    /// defined in the module in the function index space.
+    imported_functions: u32,
+    /// The range of each function body, as offsets into the payload of the
+    /// code section.
+    bodies: Vec<(u64, u64)>,
+}
+
+impl<'a> Sections<'a> {
+    fn parse(wasm: &'a [u8]) -> Result<Sections<'a>, Error> {
+        let mut sections = Sections {
+            customs: HashMap::new(),
+            names: Vec::new(),
+            imported_functions: 0,
+            bodies: Vec::new(),
+        };
+        for section in ModuleReader::new(wasm)? {
+            let section = section?;
+            match section.code {
+                SectionCode::Import => {
+                    for import in section.get_import_section_reader()? {
+                        if let ImportSectionEntryType::Function(_) = import?.ty {
+                            sections.imported_functions += 1;
+                        }
+                    }
+                }
+                SectionCode::Code => {
+                    let code = section.range().start;
+                    for body in section.get_code_section_reader()? {
+                        let range = body?.range();
+                        sections
+                            .bodies
+                            .push(((range.start - code) as u64, (range.end - code) as u64));
+                    }
+                }
+                SectionCode::Custom {
+                    kind: CustomSectionKind::Name,
+                    ..
+                } => sections.names = function_names(&section)?,
+                SectionCode::Custom { name, .. } => {
+                    let range = section.range();
+                    sections.customs.insert(name, &wasm[range.start..range.end]);
+                }
+                _ => {}
+            }
+        }
+        Ok(sections)
+    }
+}
+
+/// Reads the function names subsection of a name section.
+fn function_names(section: &Section) -> Result<Vec<(u32, String)>, Error> {
+    let mut names = Vec::new();
+    for name in section.get_name_section_reader()? {
+        let mut map = match name? {
+            Name::Function(names) => names.get_map()?,
+            _ => continue,
+        };
+        for _ in 0..map.get_count() {
+            let naming = map.read()?;
+            names.push((naming.index, naming.name.to_string()));
+        }
+    }
+    Ok(names)
+}
+
+/// Collects the source location of every address in the line programs of all
+/// compilation units, keeping the first row for addresses with several.
+fn line_rows(customs: &HashMap<&str, &[u8]>) -> Result<BTreeMap<u64, (Rc<str>, u64)>, Error> {
+    let load = |id: gimli::SectionId| -> Result<_, gimli::Error> {
+        let data = customs.get(id.name()).cloned().unwrap_or(&[]);
+        Ok(EndianSlice::new(data, LittleEndian))
+    };
+    let no_sup = |_| Ok(EndianSlice::new(&[][..], LittleEndian));
+    let dwarf = gimli::Dwarf::load(load, no_sup)?;
+
+    let mut rows = BTreeMap::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        let unit = dwarf.unit(header)?;
+        let program = match unit.line_program.clone() {
+            Some(program) => program,
+            None => continue,
+        };
+        let mut files = HashMap::<_, Rc<str>>::new();
+        let mut program = program.rows();
+        while let Some((header, row)) = program.next_row()? {
+            if row.end_sequence() {
+                continue;
+            }
+            let line = match row.line() {
+                Some(line) => line,
+                None => continue,
+            };
+            let file = match files.entry(row.file_index()) {
+                Entry::Occupied(e) => e.get().clone(),
+                Entry::Vacant(e) => e
+                    .insert(file_path(&dwarf, &unit, header, row.file_index())?)
+                    .clone(),
+            };
+            rows.entry(row.address()).or_insert((file, line));
+        }
+    }
+    Ok(rows)
+}
+
+fn file_path(
+    dwarf: &gimli::Dwarf<Reader>,
+    unit: &gimli::Unit<Reader>,
+    header: &gimli::LineProgramHeader<Reader>,
+    index: u64,
+) -> Result<Rc<str>, Error> {
+    let file = match header.file(index) {
+        Some(file) => file,
+        None => bail!("invalid file index {} in line program", index),
+    };
+    // Relative paths are relative to the directory of the file, which is
+    // relative to the compilation directory in turn.
+    let mut path = PathBuf::new();
+    if let Some(dir) = &unit.comp_dir {
+        path.push(&*dir.to_string_lossy());
+    }
+    if let Some(dir) = file.directory(header) {
+        path.push(&*dwarf.attr_string(unit, dir)?.to_string_lossy());
+    }
+    path.push(&*dwarf.attr_string(unit, file.path_name())?.to_string_lossy());
+    Ok(path.to_string_lossy().into())
+}
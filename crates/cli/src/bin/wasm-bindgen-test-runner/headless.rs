@@ -5,6 +5,7 @@ use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 use serde_json::{self, json};
 use std::env;
+use std::fs;
 use std::io::{self, Read};
 use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
@@ -19,7 +20,25 @@ use std::time::{Duration, Instant};
 /// binary, controlling it, running tests, scraping output, displaying output,
 /// etc. It will return `Ok` if all tests finish successfully, and otherwise it
 /// will return an error if some tests failed.
-pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
+///
+/// With `list` the tests are only listed by the test harness, which doesn't
+/// print test results then. If `logfile` is given the structured report of the
+/// test harness is written to it.
+pub fn run(
+    server: &SocketAddr,
+    shell: &Shell,
+    list: bool,
+    logfile: Option<&Path>,
+) -> Result<(), Error> {
+    let finished = |output: &str| {
+        if list {
+            // The listing ends with "N tests, M benchmarks", where the
+            // nouns are singular for one test or benchmark.
+            output.contains(" benchmark")
+        } else {
+            output.contains("test result: ")
+        }
+    };
     let (driver, args) = Driver::find()?;
     println!(
         "Running headless tests in {} with `{}`",
@@ -76,6 +95,7 @@ pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
     let output = client.element(&id, "#output")?;
     let logs = client.element(&id, "#console_log")?;
     let errors = client.element(&id, "#console_error")?;
+    let report = client.element(&id, "#report")?;
 
     // At this point we need to wait for the test to finish before we can take a
     // look at what happened. There appears to be no great way to do this with
@@ -90,11 +110,23 @@ pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
     //       local script" which is pretty bad to time out for, we should detect
     //       this on the page and look for such output here, printing diagnostic
     //       information.
+    //
+    // Tests which never finish are failed by the test harness itself with a
+    // per-test timeout, but the suite as a whole can still take longer than
+    // we're willing to wait. That can be configured in seconds with
+    // `WASM_BINDGEN_TEST_TIMEOUT`.
+    let max = match env::var("WASM_BINDGEN_TEST_TIMEOUT") {
+        Ok(secs) => Duration::new(
+            secs.parse()
+                .context("failed to parse `WASM_BINDGEN_TEST_TIMEOUT` as seconds")?,
+            0,
+        ),
+        Err(_) => Duration::new(20, 0),
+    };
     shell.status("Waiting for test to finish...");
     let start = Instant::now();
-    let max = Duration::new(20, 0);
     while start.elapsed() < max {
-        if client.text(&id, &output)?.contains("test result: ") {
+        if finished(&client.text(&id, &output)?) {
             break;
         }
         thread::sleep(Duration::from_millis(100));
@@ -109,7 +141,7 @@ pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
     let logs = client.text(&id, &logs)?;
     let errors = client.text(&id, &errors)?;
 
-    if output.contains("test result: ") {
+    if finished(&output) {
         println!("{}", output);
 
         // If the tests harness finished (either successfully or unsuccessfully)
@@ -117,7 +149,11 @@ pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
         // output, so we shouldn't need the driver logs to get printed.
         child.print_stdio_on_drop = false;
     } else {
-        println!("failed to detect test as having been run");
+        println!(
+            "failed to detect test as having been run within {} seconds, \
+             see `WASM_BINDGEN_TEST_TIMEOUT`",
+            max.as_secs()
+        );
         if output.len() > 0 {
             println!("output div contained:\n{}", tab(&output));
         }
@@ -129,7 +165,26 @@ pub fn run(server: &SocketAddr, shell: &Shell) -> Result<(), Error> {
         println!("console.log div contained:\n{}", tab(&errors));
     }
 
-    if !output.contains("test result: ok") {
+    // The structured report is only filled in once the test harness is done,
+    // slightly after the test results have been printed.
+    if let Some(logfile) = logfile {
+        let start = Instant::now();
+        let mut text = String::new();
+        while start.elapsed() < Duration::new(5, 0) {
+            text = client.text(&id, &report)?;
+            if !text.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        if text.is_empty() {
+            bail!("no report to write to `--logfile`, is `--format json` or `--format junit` missing?");
+        }
+        fs::write(logfile, text)
+            .with_context(|_| format!("failed to write report to {}", logfile.display()))?;
+    }
+
+    if !list && !output.contains("test result: ok") {
         bail!("some tests failed")
     }
 
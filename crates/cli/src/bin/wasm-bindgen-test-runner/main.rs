@@ -13,8 +13,9 @@
 
 use failure::{bail, format_err, Error, ResultExt};
 use std::env;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::thread;
 use wasm_bindgen_cli_support::Bindgen;
@@ -23,6 +24,8 @@ use wasm_bindgen_cli_support::Bindgen;
 #[global_allocator]
 static ALLOC: std::alloc::System = std::alloc::System;
 
+mod coverage;
+mod dwarf;
 mod headless;
 mod node;
 mod server;
@@ -45,8 +48,9 @@ fn rmain() -> Result<(), Error> {
     let mut args = env::args_os().skip(1);
     let shell = shell::Shell::new();
 
-    // Currently no flags are supported, and assume there's only one argument
-    // which is the wasm file to test. This'll want to improve over time!
+    // The first argument is the wasm file to test, and all others are flags
+    // and filters for the test harness in the style of libtest, which are
+    // forwarded to it whichever way the tests are executed.
     let wasm_file_to_test = match args.next() {
         Some(file) => PathBuf::from(file),
         None => bail!("must have a file to test as first argument"),
@@ -94,9 +98,41 @@ fn rmain() -> Result<(), Error> {
     // That's done on a per-test-binary basis with the
     // `wasm_bindgen_test_configure` macro, which emits a custom section for us
     // to read later on.
+    //
+    // The section is a sequence of options, each a tag byte possibly followed
+    // by a payload: `0x01` for `run_in_browser`, `0x02` followed by a
+    // little-endian `u32` for `concurrency = N`, `0x03` for `run_in_worker`,
+    // `0x04` for `run_in_shared_worker` and `0x05` for `run_in_node_esm`.
     let mut node = true;
+    let mut node_esm = false;
+    let mut concurrency = None;
+    let mut worker = None;
     if let Some(section) = wasm.customs.remove_raw("__wasm_bindgen_test_unstable") {
-        node = !section.data.contains(&0x01);
+        let mut data = &section.data[..];
+        while let Some((&tag, rest)) = data.split_first() {
+            data = rest;
+            match tag {
+                0x01 => node = false,
+                0x02 if data.len() >= 4 => {
+                    let n = u32::from(data[0])
+                        | u32::from(data[1]) << 8
+                        | u32::from(data[2]) << 16
+                        | u32::from(data[3]) << 24;
+                    concurrency = Some(n);
+                    data = &data[4..];
+                }
+                0x03 => {
+                    node = false;
+                    worker = Some(server::Worker::Dedicated);
+                }
+                0x04 => {
+                    node = false;
+                    worker = Some(server::Worker::Shared);
+                }
+                0x05 => node_esm = true,
+                _ => bail!("malformed `__wasm_bindgen_test_unstable` section, is `wasm-bindgen-test` a different version?"),
+            }
+        }
     }
     let headless = env::var("NO_HEADLESS").is_err();
     let debug = env::var("WASM_BINDGEN_NO_DEBUG").is_err();
@@ -130,11 +166,17 @@ integration test.\
     }
 
     // Make the generated bindings available for the tests to execute against.
+    // Workers can't import ES modules, so they get `--target no-modules`
+    // bindings loaded with `importScripts`, whereas node.js gets ES modules
+    // rather than CommonJS if asked to with `run_in_node_esm`.
+    let node_esm = node && node_esm;
     shell.status("Executing bindgen...");
     let mut b = Bindgen::new();
     b.debug(debug)
-        .nodejs(node)?
-        .web(!node)?
+        .nodejs(node && !node_esm)?
+        .nodejs_experimental_modules(node_esm)?
+        .web(!node && worker.is_none())?
+        .no_modules(worker.is_some())?
         .input_module(module, wasm)
         .keep_debug(false)
         .emit_start(false)
@@ -142,9 +184,93 @@ integration test.\
         .context("executing `wasm-bindgen` over the wasm file")?;
     shell.clear();
 
+    // Code coverage is collected by instrumenting the wasm file `wasm-bindgen`
+    // just emitted, and the lcov report is written to the path given in
+    // `WASM_BINDGEN_TEST_COVERAGE` once the tests have finished.
+    let coverage = match env::var_os("WASM_BINDGEN_TEST_COVERAGE") {
+        Some(output) => {
+            if !node || node_esm {
+                bail!(
+                    "code coverage is currently only supported for tests run in \
+                     node.js without `run_in_node_esm`"
+                );
+            }
+            let wasm_path = tmpdir.join(format!("{}_bg.wasm", module));
+            let coverage = coverage::instrument(&wasm_path, &wasm_file_to_test, Path::new(&output))
+                .context("failed to instrument wasm file for code coverage")?;
+            Some(coverage)
+        }
+        None => None,
+    };
+
+    // The `--logfile` flag is handled here rather than by the test harness:
+    // the structured report requested with `--format json` or
+    // `--format junit` is written to that file.
+    //
+    // Similarly `--baseline` names a JSON report of a previous run to compare
+    // benchmarks against, which the test harness can't read itself, so its
+    // contents are passed along with `--baseline-data` instead.
+    let mut logfile = None;
+    let mut harness_args = Vec::new();
+    while let Some(arg) = args.next() {
+        let flag = arg.to_str().unwrap_or("");
+        let baseline = if flag == "--baseline" {
+            match args.next() {
+                Some(path) => Some(PathBuf::from(path)),
+                None => bail!("`--baseline` requires a path"),
+            }
+        } else if flag.starts_with("--baseline=") {
+            Some(PathBuf::from(&flag["--baseline=".len()..]))
+        } else {
+            None
+        };
+        if let Some(path) = baseline {
+            let data = fs::read_to_string(&path)
+                .with_context(|_| format!("failed to read baseline `{}`", path.display()))?;
+            harness_args.push("--baseline-data".into());
+            harness_args.push(data.into());
+        } else if flag == "--logfile" {
+            match args.next() {
+                Some(path) => logfile = Some(PathBuf::from(path)),
+                None => bail!("`--logfile` requires a path"),
+            }
+        } else if flag.starts_with("--logfile=") {
+            logfile = Some(PathBuf::from(&flag["--logfile=".len()..]));
+        } else {
+            harness_args.push(arg);
+        }
+    }
+    let args = harness_args;
+
+    // The structured report can't be told apart from the regular output on
+    // stdout, so it must be written to a file, except in an interactive
+    // browser where the report is shown on the page.
+    if logfile.is_none() && (node || headless) && report_requested(&args) {
+        bail!("`--format json` and `--format junit` require `--logfile PATH`");
+    }
+
+    // The configured concurrency is passed to the test harness as if it were
+    // given on the command line, ahead of the actual arguments so that
+    // `--test-threads` passed there takes precedence.
+    let args = match concurrency {
+        Some(n) => vec!["--test-threads".into(), n.to_string().into()]
+            .into_iter()
+            .chain(args)
+            .collect::<Vec<OsString>>(),
+        None => args,
+    };
+
     // If we're executing in node.js, that module will take it from here.
     if node {
-        return node::execute(&module, &tmpdir, &args.collect::<Vec<_>>(), &tests);
+        return node::execute(
+            &module,
+            &tmpdir,
+            &args,
+            &tests,
+            logfile.as_ref().map(|p| &**p),
+            coverage.as_ref(),
+            node_esm,
+        );
     }
 
     // Otherwise we're executing in a browser. Spawn a server which serves up
@@ -158,8 +284,9 @@ integration test.\
         headless,
         &module,
         &tmpdir,
-        &args.collect::<Vec<_>>(),
+        &args,
         &tests,
+        worker,
     )
     .context("failed to spawn server")?;
     let addr = srv.server_addr();
@@ -180,6 +307,22 @@ integration test.\
     }
 
     thread::spawn(|| srv.run());
-    headless::run(&addr, &shell)?;
+    let list = args.iter().any(|arg| arg == "--list");
+    headless::run(&addr, &shell, list, logfile.as_ref().map(|p| &**p))?;
     Ok(())
 }
+
+/// Returns whether `args` ask the test harness for a structured report with
+/// `--format json` or `--format junit`.
+fn report_requested(args: &[OsString]) -> bool {
+    let mut format = None;
+    let mut args = args.iter().map(|arg| arg.to_str().unwrap_or(""));
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = args.next();
+        } else if arg.starts_with("--format=") {
+            format = Some(&arg["--format=".len()..]);
+        }
+    }
+    format == Some("json") || format == Some("junit")
+}
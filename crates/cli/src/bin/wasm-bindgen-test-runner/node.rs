@@ -6,15 +6,55 @@ use std::process::Command;
 
 use failure::{Error, ResultExt};
 
+use crate::coverage::Coverage;
+
 pub fn execute(
     module: &str,
     tmpdir: &Path,
     args: &[OsString],
     tests: &[String],
+    logfile: Option<&Path>,
+    coverage: Option<&Coverage>,
+    esm: bool,
 ) -> Result<(), Error> {
+    // The path of the file to write the structured report to, if any, as a JS
+    // string or `null`.
+    let logfile = serde_json::to_string(&logfile.map(|p| p.to_string_lossy()))?;
+    // The counters the wasm module was instrumented with for code coverage,
+    // if any, as a JS object or `null`.
+    let coverage = serde_json::to_string(&coverage)?;
+
+    // With `run_in_node_esm` the bindings are ES modules, so the script which
+    // executes the tests is one too and loads them with `import()`. The wasm
+    // shim is imported first: it imports the bindings in turn, which have to
+    // be fully evaluated by the time it instantiates the wasm module.
+    let (header, load) = if esm {
+        (
+            "import { exit } from 'process';\n\
+             import * as fs from 'fs';\n\
+             import * as perf_hooks from 'perf_hooks';",
+            format!(
+                "const wasm = await import('./{0}_bg.mjs');\n\
+                 const support = await import('./{0}.mjs');",
+                module
+            ),
+        )
+    } else {
+        (
+            "const { exit } = require('process');\n\
+             const fs = require('fs');\n\
+             const perf_hooks = require('perf_hooks');",
+            format!(
+                "const support = require('./{0}');\n\
+                 const wasm = require('./{0}_bg');",
+                module
+            ),
+        )
+    };
+
     let mut js_to_execute = format!(
         r#"
-        const {{ exit }} = require('process');
+        {0}
 
         const handlers = {{}};
 
@@ -40,11 +80,15 @@ pub fn execute(
 
         global.__wbg_test_invoke = f => f();
 
+        // Benchmarks are timed with `performance.now()`, which older versions
+        // of node.js only provide through the `perf_hooks` module.
+        if (typeof performance === 'undefined')
+            global.performance = perf_hooks.performance;
+
         async function main(tests) {{
-            const support = require("./{0}");
-            const wasm = require("./{0}_bg");
+            {1}
 
-            cx = new support.WasmBindgenTestContext();
+            const cx = new support.WasmBindgenTestContext();
             handlers.on_console_debug = support.__wbgtest_console_debug;
             handlers.on_console_log = support.__wbgtest_console_log;
             handlers.on_console_info = support.__wbgtest_console_info;
@@ -53,18 +97,91 @@ pub fn execute(
 
             // Forward runtime arguments. These arguments are also arguments to the
             // `wasm-bindgen-test-runner` which forwards them to node which we
-            // forward to the test harness. this is basically only used for test
-            // filters for now.
+            // forward to the test harness, such as test filters and flags like
+            // `--exact` or `--nocapture`.
             cx.args(process.argv.slice(2));
 
             const ok = await cx.run(tests.map(n => wasm[n]));
+
+            // Write out the structured report requested with `--format`, if
+            // any, to the file given with `--logfile`.
+            const report = cx.report();
+            const logfile = {2};
+            if (report !== undefined && logfile !== null)
+                fs.writeFileSync(logfile, report);
+
+            const coverage = {3};
+            if (coverage !== null)
+                writeCoverage(coverage, wasm);
+
             if (!ok)
                 exit(1);
         }}
 
+        // Reads out the counters of an instrumented wasm module and writes
+        // them as an lcov report with a record for each source file. Each
+        // function is recorded at the line it starts at, along with its
+        // blocks as branches of that line, and functions without line
+        // information at line 0 of the wasm file.
+        function writeCoverage(coverage, wasm) {{
+            const ptr = wasm.__wbgtest_coverage_dump();
+            if (ptr < 0)
+                throw new Error('failed to allocate memory for coverage counters');
+            const counts = new Uint32Array(wasm.memory.buffer, ptr, coverage.counters);
+
+            const files = new Map();
+            coverage.functions.forEach((f, i) => {{
+                const file = f.file === null ? coverage.source : f.file;
+                if (!files.has(file))
+                    files.set(file, []);
+                files.get(file).push([f, i]);
+            }});
+
+            let lcov = '';
+            for (const [file, functions] of files) {{
+                lcov += `TN:\nSF:${{file}}\n`;
+                let hit = 0;
+                let branches = 0;
+                let branchesHit = 0;
+                const lines = new Map();
+                for (const [f] of functions)
+                    lcov += `FN:${{f.line}},${{f.name}}\n`;
+                for (const [f] of functions) {{
+                    const count = counts[f.first];
+                    lcov += `FNDA:${{count}},${{f.name}}\n`;
+                    if (count > 0)
+                        hit += 1;
+                    if (f.line > 0)
+                        lines.set(f.line, (lines.get(f.line) || 0) + count);
+                }}
+                lcov += `FNF:${{functions.length}}\nFNH:${{hit}}\n`;
+                for (const [f, i] of functions) {{
+                    for (let j = 1; j < f.blocks; j++) {{
+                        const count = counts[f.first + j];
+                        lcov += `BRDA:${{f.line}},${{i}},${{j - 1}},${{count}}\n`;
+                        branches += 1;
+                        if (count > 0)
+                            branchesHit += 1;
+                    }}
+                }}
+                lcov += `BRF:${{branches}}\nBRH:${{branchesHit}}\n`;
+                if (lines.size > 0) {{
+                    let linesHit = 0;
+                    for (const [line, count] of [...lines].sort((a, b) => a[0] - b[0])) {{
+                        lcov += `DA:${{line}},${{count}}\n`;
+                        if (count > 0)
+                            linesHit += 1;
+                    }}
+                    lcov += `LF:${{lines.size}}\nLH:${{linesHit}}\n`;
+                }}
+                lcov += 'end_of_record\n';
+            }}
+            fs.writeFileSync(coverage.output, lcov);
+        }}
+
         const tests = [];
     "#,
-        module
+        header, load, logfile, coverage,
     );
 
     // Note that we're collecting *JS objects* that represent the functions to
@@ -84,12 +201,21 @@ pub fn execute(
     ",
     );
 
-    let js_path = tmpdir.join("run.js");
+    // JS snippets are written out as `.js` files, which node.js only treats as
+    // ES modules if the closest `package.json` says so.
+    let snippets = tmpdir.join("snippets");
+    if esm && snippets.exists() {
+        fs::write(snippets.join("package.json"), r#"{ "type": "module" }"#)
+            .context("failed to write package.json for JS snippets")?;
+    }
+
+    let js_path = tmpdir.join(if esm { "run.mjs" } else { "run.js" });
     fs::write(&js_path, js_to_execute).context("failed to write JS file")?;
 
     // Augment `NODE_PATH` so things like `require("tests/my-custom.js")` work
     // and Rust code can import from custom JS shims. This is a bit of a hack
-    // and should probably be removed at some point.
+    // and should probably be removed at some point. Note that it has no effect
+    // on imports from ES modules.
     let path = env::var("NODE_PATH").unwrap_or_default();
     let mut path = env::split_paths(&path).collect::<Vec<_>>();
     path.push(env::current_dir().unwrap());
@@ -100,13 +226,13 @@ pub fn execute(
         .map(|s| s.to_string())
         .filter(|s| !s.is_empty())
         .collect::<Vec<_>>();
-    exec(
-        Command::new("node")
-            .env("NODE_PATH", env::join_paths(&path).unwrap())
-            .args(&extra_node_args)
-            .arg(&js_path)
-            .args(args),
-    )
+    let mut cmd = Command::new("node");
+    cmd.env("NODE_PATH", env::join_paths(&path).unwrap())
+        .args(&extra_node_args);
+    if esm {
+        cmd.arg("--experimental-modules");
+    }
+    exec(cmd.arg(&js_path).args(args))
 }
 
 #[cfg(unix)]
@@ -6,6 +6,14 @@ use std::path::Path;
 use failure::{format_err, Error, ResultExt};
 use rouille::{Request, Response, Server};
 
+/// The kinds of web workers tests can be executed in, as configured with
+/// `wasm_bindgen_test_configure!`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Worker {
+    Dedicated,
+    Shared,
+}
+
 pub fn spawn(
     addr: &SocketAddr,
     headless: bool,
@@ -13,7 +21,14 @@ pub fn spawn(
     tmpdir: &Path,
     args: &[OsString],
     tests: &[String],
+    worker: Option<Worker>,
 ) -> Result<Server<impl Fn(&Request) -> Response + Send + Sync>, Error> {
+    // Arguments are passed to the test harness as a JSON array of strings.
+    let args = args
+        .iter()
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>();
+    let args = serde_json::to_string(&args)?;
     let mut js_to_execute = format!(
         r#"
         import {{
@@ -43,11 +58,17 @@ pub fn spawn(
 
             // Forward runtime arguments. These arguments are also arguments to the
             // `wasm-bindgen-test-runner` which forwards them to node which we
-            // forward to the test harness. this is basically only used for test
-            // filters for now.
-            cx.args({1:?});
+            // forward to the test harness, such as test filters and flags like
+            // `--exact` or `--nocapture`.
+            cx.args({1});
 
             await cx.run(test.map(s => wasm[s]));
+
+            // Show the structured report requested with `--format`, if any,
+            // from where headless testing can scrape it.
+            const report = cx.report();
+            if (report !== undefined)
+                document.getElementById('report').textContent = report;
         }}
 
         const tests = [];
@@ -59,6 +80,14 @@ pub fn spawn(
     }
     js_to_execute.push_str("main(tests);\n");
 
+    // In a worker the tests are instead executed by `worker.js`, and `run.js`
+    // on the page only starts the worker and renders the output it relays.
+    if let Some(worker) = worker {
+        let worker_js = worker_js(worker, module, &args, tests)?;
+        fs::write(tmpdir.join("worker.js"), worker_js).context("failed to write JS file")?;
+        js_to_execute = page_js(worker);
+    }
+
     let js_path = tmpdir.join("run.js");
     fs::write(&js_path, js_to_execute).context("failed to write JS file")?;
 
@@ -123,3 +152,149 @@ pub fn spawn(
         response
     }
 }
+
+/// Generates the script executing the tests in a worker, which is loaded with
+/// `--target no-modules` bindings as workers can't import ES modules.
+///
+/// All output is relayed to the page with `postMessage`: output of the test
+/// harness as `["writeln", line]`, console output of tests as
+/// `["console", method, args]` and the report requested with `--format` as
+/// `["report", report]`.
+fn worker_js(worker: Worker, module: &str, args: &str, tests: &[String]) -> Result<String, Error> {
+    let start = match worker {
+        Worker::Dedicated => "post = msg => self.postMessage(msg);\nmain().catch(fail);",
+        // A shared worker talks to pages through the ports they're connected
+        // with. The tests only run once, when the first page connects, and
+        // their output is sent to every page, including what was sent before
+        // a page connected.
+        Worker::Shared => {
+            "const ports = [];
+            const messages = [];
+            post = msg => {
+                messages.push(msg);
+                for (const port of ports)
+                    port.postMessage(msg);
+            };
+            self.onconnect = e => {
+                const port = e.ports[0];
+                ports.push(port);
+                for (const msg of messages)
+                    port.postMessage(msg);
+                if (ports.length === 1)
+                    main().catch(fail);
+            };"
+        }
+    };
+    Ok(format!(
+        r#"
+        importScripts('{0}.js');
+
+        let post;
+
+        // Capture the console output of tests like on the main thread, and
+        // relay it to the page which renders it.
+        const wrap = method => {{
+            const on_method = `on_console_${{method}}`;
+            console[method] = function (...args) {{
+                if (self[on_method]) {{
+                    self[on_method](args);
+                }}
+                post(["console", method, args.map(String)]);
+            }};
+        }};
+
+        wrap("debug");
+        wrap("log");
+        wrap("info");
+        wrap("warn");
+        wrap("error");
+
+        self.__wbg_test_invoke = f => f();
+        self.__wbg_test_output_writeln = line => post(["writeln", line]);
+
+        // Errors outside of the tests, such as failing to instantiate the wasm
+        // module, are relayed as output so they show up on the page.
+        const fail = e => post(["writeln", `error: ${{e && e.stack || e}}`]);
+
+        let wasm;
+
+        async function main() {{
+            if (wasm === undefined)
+                wasm = wasm_bindgen('./{0}_bg.wasm');
+            const exports = await wasm;
+
+            const cx = new wasm_bindgen.WasmBindgenTestContext();
+            self.on_console_debug = wasm_bindgen.__wbgtest_console_debug;
+            self.on_console_log = wasm_bindgen.__wbgtest_console_log;
+            self.on_console_info = wasm_bindgen.__wbgtest_console_info;
+            self.on_console_warn = wasm_bindgen.__wbgtest_console_warn;
+            self.on_console_error = wasm_bindgen.__wbgtest_console_error;
+
+            cx.args({1});
+
+            await cx.run({2}.map(s => exports[s]));
+
+            const report = cx.report();
+            if (report !== undefined)
+                post(["report", report]);
+        }}
+
+        {3}
+    "#,
+        module,
+        args,
+        serde_json::to_string(tests)?,
+        start,
+    ))
+}
+
+/// Generates the script for the page when the tests are executed in a
+/// worker, which starts the worker and renders what it relays.
+fn page_js(worker: Worker) -> String {
+    let start = match worker {
+        Worker::Dedicated => {
+            "const worker = new Worker('worker.js');
+            const port = worker;"
+        }
+        Worker::Shared => {
+            "const worker = new SharedWorker('worker.js');
+            const port = worker.port;
+            port.start();"
+        }
+    };
+    format!(
+        r#"
+        document.getElementById('output').textContent = "Loading wasm module in a worker...";
+
+        {}
+
+        let output = '';
+        const writeln = line => {{
+            output += `${{line}}\n`;
+            document.getElementById('output').textContent = output;
+        }};
+
+        // Uncaught errors in the worker, including failing to load its
+        // script, and messages which can't be received are written out too,
+        // as otherwise the page would just keep waiting for the tests.
+        worker.onerror = e => writeln(`error in worker: ${{e.message || 'failed to load worker.js'}}`);
+        port.onmessageerror = () => writeln('error: failed to receive a message from the worker');
+
+        port.onmessage = e => {{
+            const [kind, ...data] = e.data;
+            switch (kind) {{
+                case "writeln":
+                    writeln(data[0]);
+                    break;
+                case "console":
+                    console[data[0]](...data[1]);
+                    break;
+                case "report":
+                    document.getElementById('report').textContent = data[0];
+                    break;
+            }}
+        }};
+    "#,
+        start
+    )
+}
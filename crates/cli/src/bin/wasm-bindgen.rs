@@ -35,6 +35,10 @@ Options:
     --remove-producers-section   Remove the telemetry `producers` section
     --encode-into MODE           Whether or not to use TextEncoder#encodeInto,
                                  valid values are [test, always, never]
+    --weak-refs                  Free Rust memory owned by JS objects when they
+                                 are garbage collected
+    --heap-snapshot              Record where each JsValue was allocated for
+                                 `wasm_bindgen::heap_snapshot`
     --nodejs                     Deprecated, use `--target nodejs`
     --web                        Deprecated, use `--target web`
     --no-modules                 Deprecated, use `--target no-modules`
@@ -59,6 +63,8 @@ struct Args {
     flag_remove_producers_section: bool,
     flag_keep_debug: bool,
     flag_encode_into: Option<String>,
+    flag_weak_refs: bool,
+    flag_heap_snapshot: bool,
     flag_target: Option<String>,
     arg_input: Option<PathBuf>,
 }
@@ -119,6 +125,12 @@ fn rmain(args: &Args) -> Result<(), Error> {
     if let Some(ref name) = args.flag_out_name {
         b.out_name(name);
     }
+    if args.flag_weak_refs {
+        b.weak_refs(true);
+    }
+    if args.flag_heap_snapshot {
+        b.heap_snapshot(true);
+    }
     if let Some(mode) = &args.flag_encode_into {
         match mode.as_str() {
             "test" => b.encode_into(EncodeInto::Test),
@@ -7,14 +7,18 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use futures_channel::oneshot;
-use futures_util::future::FutureExt;
+use futures_util::future::{poll_fn, FutureExt};
+use futures_util::stream::Stream;
 use futures_util::task::ArcWake;
 
 use lazy_static::lazy_static;
 
 use js_sys::Promise;
+use wasm_bindgen::convert::IntoWasmAbi;
 use wasm_bindgen::prelude::*;
 
+use crate::stream_iterator;
+
 /// A Rust `Future` backed by a JavaScript `Promise`.
 ///
 /// This type is constructed with a JavaScript `Promise` object and translates
@@ -269,3 +273,25 @@ where
 
     ArcWake::wake_by_ref(&Task::new(future));
 }
+
+/// Converts a Rust `Stream` into a JavaScript async iterator, which can be
+/// consumed with a `for await` loop.
+///
+/// This works the same as the `stream_to_async_iterator` function at the root
+/// of this crate, except that errors are `Err` items of the stream.
+pub fn stream_to_async_iterator<S, T>(stream: S) -> JsValue
+where
+    S: Stream<Item = Result<T, JsValue>> + 'static,
+    T: IntoWasmAbi + 'static,
+{
+    stream_iterator::new(Box::pin(stream), |state| {
+        let state2 = state.clone();
+        let next = poll_fn(move |cx| match state2.borrow_mut().stream.as_mut() {
+            Some(stream) => stream.as_mut().poll_next(cx),
+            None => Poll::Ready(None),
+        });
+        future_to_promise(
+            next.map(move |result| stream_iterator::item_received(&state, result.transpose())),
+        )
+    })
+}
@@ -107,6 +107,8 @@
 /// Contains a Futures 0.3 implementation of this crate.
 pub mod futures_0_3;
 
+mod stream_iterator;
+
 use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::rc::Rc;
@@ -117,6 +119,7 @@ use futures::future;
 use futures::prelude::*;
 use futures::sync::oneshot;
 use js_sys::{Function, Promise};
+use wasm_bindgen::convert::IntoWasmAbi;
 use wasm_bindgen::prelude::*;
 
 /// A Rust `Future` backed by a JavaScript `Promise`.
@@ -404,3 +407,29 @@ where
             .or_else(|()| future::ok::<JsValue, JsValue>(JsValue::undefined())),
     );
 }
+
+/// Converts a Rust `Stream` into a JavaScript async iterator, which can be
+/// consumed with a `for await` loop.
+///
+/// The stream is only polled while JavaScript waits for its next item, and its
+/// items are converted to JavaScript values in the same way as the items of a
+/// `wasm_bindgen::JsIterator`. An error from the stream rejects the promise
+/// returned by the iterator's `next` method and ends the iteration.
+///
+/// The stream is dropped once it finishes or JavaScript ends the iteration
+/// early, for example by breaking out of a `for await` loop. If JavaScript
+/// stops iterating without doing either, the stream is leaked.
+pub fn stream_to_async_iterator<S>(stream: S) -> JsValue
+where
+    S: Stream<Error = JsValue> + 'static,
+    S::Item: IntoWasmAbi + 'static,
+{
+    stream_iterator::new(stream, |state| {
+        let state2 = state.clone();
+        let next = future::poll_fn(move || match state2.borrow_mut().stream.as_mut() {
+            Some(stream) => stream.poll(),
+            None => Ok(Async::Ready(None)),
+        });
+        future_to_promise(next.then(move |result| stream_iterator::item_received(&state, result)))
+    })
+}
@@ -0,0 +1,92 @@
+//! Converting streams into JavaScript async iterators, shared by the
+//! implementations for both versions of the `futures` crate which only differ
+//! in how the stream is polled.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::Promise;
+use wasm_bindgen::convert::IntoWasmAbi;
+use wasm_bindgen::iter::{__make_iterator, IteratorClosures};
+use wasm_bindgen::prelude::*;
+
+/// A stream being iterated over by JavaScript.
+pub(crate) struct StreamState<S, T> {
+    /// The stream, until it finishes or JavaScript ends the iteration.
+    pub(crate) stream: Option<S>,
+    item: Option<T>,
+    closures: Option<IteratorClosures<T>>,
+}
+
+/// Creates an async iterator for `stream`.
+///
+/// Whenever JavaScript asks for the next item `next` is called, which returns
+/// a promise that polls the stream and settles with what `item_received`
+/// returns for the result.
+pub(crate) fn new<S, T, F>(stream: S, mut next: F) -> JsValue
+where
+    S: 'static,
+    T: IntoWasmAbi + 'static,
+    F: FnMut(Rc<RefCell<StreamState<S, T>>>) -> Promise + 'static,
+{
+    let state = Rc::new(RefCell::new(StreamState {
+        stream: Some(stream),
+        item: None,
+        closures: None,
+    }));
+
+    let state2 = state.clone();
+    let advance = Closure::wrap(Box::new(move |stop: bool| {
+        if stop {
+            finish(&state2);
+            return JsValue::FALSE;
+        }
+        next(state2.clone()).into()
+    }) as Box<dyn FnMut(bool) -> JsValue>);
+
+    let state2 = state.clone();
+    let take = Closure::wrap(Box::new(move || {
+        state2
+            .borrow_mut()
+            .item
+            .take()
+            .expect_throw("stream item taken more than once")
+    }) as Box<dyn FnMut() -> T>);
+
+    let ret = __make_iterator(advance.as_ref(), take.as_ref(), true);
+    state.borrow_mut().closures = Some(IteratorClosures { advance, take });
+    ret
+}
+
+/// Records the result of polling the stream for its next item, returning
+/// whether there is one for JavaScript to take. The iteration ends once the
+/// stream has finished or failed.
+pub(crate) fn item_received<S, T>(
+    state: &RefCell<StreamState<S, T>>,
+    result: Result<Option<T>, JsValue>,
+) -> Result<JsValue, JsValue> {
+    match result {
+        Ok(Some(item)) => {
+            state.borrow_mut().item = Some(item);
+            Ok(JsValue::TRUE)
+        }
+        Ok(None) => {
+            finish(state);
+            Ok(JsValue::FALSE)
+        }
+        Err(e) => {
+            finish(state);
+            Err(e)
+        }
+    }
+}
+
+/// Drops the stream along with the `Closure`s driving the iterator, which
+/// also breaks their reference cycle with the state.
+fn finish<S, T>(state: &RefCell<StreamState<S, T>>) {
+    let mut state = state.borrow_mut();
+    state.stream = None;
+    let closures = state.closures.take();
+    drop(state);
+    drop(closures);
+}
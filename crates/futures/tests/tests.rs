@@ -1,6 +1,8 @@
 #![cfg(target_arch = "wasm32")]
 
 extern crate futures;
+#[cfg(feature = "futures_0_3")]
+extern crate futures_util;
 extern crate js_sys;
 extern crate wasm_bindgen;
 extern crate wasm_bindgen_futures;
@@ -9,7 +11,8 @@ extern crate wasm_bindgen_test;
 use futures::unsync::oneshot;
 use futures::Future;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::{future_to_promise, spawn_local, JsFuture};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{future_to_promise, spawn_local, stream_to_async_iterator, JsFuture};
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen_test(async)]
@@ -116,3 +119,84 @@ fn can_create_multiple_futures_from_same_promise() -> impl Future<Item = (), Err
     let b = JsFuture::from(promise);
     futures::future::join_all(vec![a, b]).map(|_| ())
 }
+
+fn async_next(iter: &JsValue) -> impl Future<Item = (JsValue, bool), Error = JsValue> {
+    let next = js_sys::Reflect::get(iter, &JsValue::from("next")).unwrap();
+    let promise = next
+        .unchecked_into::<js_sys::Function>()
+        .call0(iter)
+        .unwrap();
+    JsFuture::from(promise.unchecked_into::<js_sys::Promise>()).map(|result| {
+        let value = js_sys::Reflect::get(&result, &JsValue::from("value")).unwrap();
+        let done = js_sys::Reflect::get(&result, &JsValue::from("done")).unwrap();
+        (value, done.as_bool().unwrap())
+    })
+}
+
+#[wasm_bindgen_test(async)]
+fn stream_to_async_iterator_yields_items() -> impl Future<Item = (), Error = JsValue> {
+    let stream = futures::stream::iter_ok::<_, JsValue>(vec![1u32, 2]);
+    let iter = stream_to_async_iterator(stream);
+    async_next(&iter).and_then(move |(value, done)| {
+        assert_eq!(value, 1);
+        assert!(!done);
+        async_next(&iter).and_then(move |(value, done)| {
+            assert_eq!(value, 2);
+            assert!(!done);
+            async_next(&iter).map(|(value, done)| {
+                assert!(value.is_undefined());
+                assert!(done);
+            })
+        })
+    })
+}
+
+#[wasm_bindgen_test(async)]
+fn stream_to_async_iterator_rejects_on_error() -> impl Future<Item = (), Error = JsValue> {
+    let stream = futures::stream::iter_result(vec![Ok(1u32), Err(JsValue::from(42))]);
+    let iter = stream_to_async_iterator(stream);
+    async_next(&iter).and_then(move |(value, _)| {
+        assert_eq!(value, 1);
+        async_next(&iter)
+            .map(|_| unreachable!())
+            .or_else(move |e| {
+                assert_eq!(e, 42);
+                async_next(&iter)
+            })
+            .map(|(_, done)| assert!(done))
+    })
+}
+
+#[cfg(feature = "futures_0_3")]
+pub mod futures_0_3 {
+    use super::async_next;
+    use futures::Future;
+    use futures_util::stream;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::futures_0_3::stream_to_async_iterator;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test(async)]
+    fn stream_to_async_iterator_works() -> impl Future<Item = (), Error = JsValue> {
+        let stream = stream::iter(vec![Ok(1u32), Ok(2), Err(JsValue::from(42))]);
+        let iter = stream_to_async_iterator(stream);
+        async_next(&iter).and_then(move |(value, done)| {
+            assert_eq!(value, 1);
+            assert!(!done);
+            async_next(&iter).and_then(move |(value, done)| {
+                assert_eq!(value, 2);
+                assert!(!done);
+                async_next(&iter)
+                    .map(|_| unreachable!())
+                    .or_else(move |e| {
+                        assert_eq!(e, 42);
+                        async_next(&iter)
+                    })
+                    .map(|(value, done)| {
+                        assert!(value.is_undefined());
+                        assert!(done);
+                    })
+            })
+        })
+    }
+}
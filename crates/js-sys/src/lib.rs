@@ -19,7 +19,6 @@
 #![doc(html_root_url = "https://docs.rs/js-sys/0.2")]
 
 use std::fmt;
-use std::mem;
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -662,6 +661,157 @@ pub mod Atomics {
     }
 }
 
+// BigInt
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Object, is_type_of = JsValue::is_bigint)]
+    #[derive(Clone, PartialEq, Eq)]
+    pub type BigInt;
+
+    #[wasm_bindgen(catch, js_name = BigInt)]
+    fn new_bigint(value: &JsValue) -> Result<BigInt, JsValue>;
+
+    /// The `BigInt.asIntN()` static method clamps a `BigInt` value to a
+    /// signed integer value, and returns that value.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt/asIntN)
+    #[wasm_bindgen(static_method_of = BigInt, js_name = asIntN)]
+    pub fn as_int_n(bits: f64, bigint: &BigInt) -> BigInt;
+
+    /// The `BigInt.asUintN()` static method clamps a `BigInt` value to an
+    /// unsigned integer value, and returns that value.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt/asUintN)
+    #[wasm_bindgen(static_method_of = BigInt, js_name = asUintN)]
+    pub fn as_uint_n(bits: f64, bigint: &BigInt) -> BigInt;
+
+    /// The `toLocaleString()` method returns a string with a
+    /// language-sensitive representation of this `BigInt`.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt/toLocaleString)
+    #[wasm_bindgen(method, js_name = toLocaleString)]
+    pub fn to_locale_string(this: &BigInt, locales: &JsValue, options: &JsValue) -> JsString;
+
+    /// The `toString()` method returns a string representing the specified
+    /// `BigInt` value in the given radix.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt/toString)
+    #[wasm_bindgen(catch, method, js_name = toString)]
+    pub fn to_string(this: &BigInt, radix: u8) -> Result<JsString, JsValue>;
+
+    /// The `valueOf()` method returns the wrapped primitive value of a
+    /// `BigInt` object.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt/valueOf)
+    #[wasm_bindgen(method, js_name = valueOf)]
+    pub fn value_of(this: &BigInt) -> BigInt;
+}
+
+impl BigInt {
+    /// The `BigInt()` function converts a number, string or boolean to a
+    /// `BigInt`.
+    ///
+    /// Returns an error if `value` can't be converted, for example if it's a
+    /// number with a fractional part or a string which isn't an integer.
+    ///
+    /// [MDN documentation](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt)
+    pub fn new(value: &JsValue) -> Result<BigInt, JsValue> {
+        new_bigint(value)
+    }
+
+    /// Computes `self ** rhs` with the JS exponentiation operator.
+    pub fn pow(&self, rhs: &BigInt) -> BigInt {
+        BigInt::unchecked_from_js(JsValue::pow(self.as_ref(), rhs.as_ref()))
+    }
+}
+
+macro_rules! bigint_from {
+    ($($x:ident)*) => ($(
+        impl From<$x> for BigInt {
+            #[inline]
+            fn from(x: $x) -> BigInt {
+                BigInt::unchecked_from_js(JsValue::from(x))
+            }
+        }
+    )*)
+}
+bigint_from!(i64 u64 i128 u128);
+
+macro_rules! bigint_from_small {
+    ($($x:ident)*) => ($(
+        impl From<$x> for BigInt {
+            #[inline]
+            fn from(x: $x) -> BigInt {
+                BigInt::from(i64::from(x))
+            }
+        }
+    )*)
+}
+bigint_from_small!(i8 u8 i16 u16 i32 u32);
+
+macro_rules! bigint_ops {
+    ($($t:ident $f:ident,)*) => ($(
+        impl<'a, 'b> std::ops::$t<&'b BigInt> for &'a BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn $f(self, rhs: &'b BigInt) -> BigInt {
+                let lhs: &JsValue = self.as_ref();
+                BigInt::unchecked_from_js(std::ops::$t::$f(lhs, rhs.as_ref()))
+            }
+        }
+
+        impl std::ops::$t<BigInt> for BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn $f(self, rhs: BigInt) -> BigInt {
+                std::ops::$t::$f(&self, &rhs)
+            }
+        }
+    )*)
+}
+
+bigint_ops! {
+    Add add,
+    Sub sub,
+    Mul mul,
+    Div div,
+    Rem rem,
+    BitAnd bitand,
+    BitOr bitor,
+    BitXor bitxor,
+    Shl shl,
+    Shr shr,
+}
+
+impl<'a> std::ops::Neg for &'a BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn neg(self) -> BigInt {
+        &BigInt::from(0) - self
+    }
+}
+
+impl std::ops::Neg for BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_string(10) {
+            Ok(s) => write!(f, "{}", String::from(s)),
+            Err(_) => f.write_str("BigInt"),
+        }
+    }
+}
+
 // Boolean
 #[wasm_bindgen]
 extern "C" {
@@ -4552,6 +4702,8 @@ macro_rules! arrays {
             /// backing buffer isn't resized in JS. Once this function is called
             /// any future calls to `Box::new` (or malloc of any form) may cause
             /// the returned value here to be invalidated. Use with caution!
+            /// In debug builds `copy_to`, `copy_from` and `to_vec` panic when
+            /// called on a view which has been invalidated this way.
             ///
             /// Additionally the returned object can be safely mutated but the
             /// input slice isn't guaranteed to be mutable.
@@ -4581,12 +4733,43 @@ macro_rules! arrays {
             /// This function will panic if this typed array's length is
             /// different than the length of the provided `dst` array.
             pub fn copy_to(&self, dst: &mut [$ty]) {
+                debug_assert_attached(&self.buffer());
                 assert_eq!(self.length() as usize, dst.len());
-                let buf = wasm_bindgen::memory();
-                let mem = buf.unchecked_ref::<WebAssembly::Memory>();
-                let all_wasm_memory = $name::new(&mem.buffer());
-                let offset = dst.as_ptr() as usize / mem::size_of::<$ty>();
-                all_wasm_memory.set(self, offset as u32);
+                unsafe { $name::view(dst).set(self, 0) }
+            }
+
+            /// Copy the contents of the source Rust slice into this
+            /// JS typed array.
+            ///
+            /// This function will efficiently copy the memory from within
+            /// the wasm module's own linear memory to this typed array.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if this typed array's length is
+            /// different than the length of the provided `src` array.
+            pub fn copy_from(&self, src: &[$ty]) {
+                debug_assert_attached(&self.buffer());
+                assert_eq!(self.length() as usize, src.len());
+                self.set(unsafe { &$name::view(src) }, 0)
+            }
+
+            /// Efficiently copies the contents of this JS typed array into a
+            /// new Rust vector.
+            pub fn to_vec(&self) -> Vec<$ty> {
+                let mut output = vec![<$ty>::default(); self.length() as usize];
+                self.copy_to(&mut output);
+                output
+            }
+        }
+
+        impl<'a> From<&'a [$ty]> for $name {
+            /// Creates a new JS typed array holding a copy of the Rust slice.
+            #[inline]
+            fn from(slice: &'a [$ty]) -> $name {
+                let array = $name::new_with_length(slice.len() as u32);
+                array.copy_from(slice);
+                array
             }
         }
     )*);
@@ -4628,4 +4811,33 @@ arrays! {
     /// `Float64Array()`
     /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Float64Array
     Float64Array: f64,
+
+    /// `BigInt64Array()`
+    /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt64Array
+    BigInt64Array: i64,
+
+    /// `BigUint64Array()`
+    /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigUint64Array
+    BigUint64Array: u64,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // Creating a view of a detached buffer throws, which is the only way to
+    // tell it apart from an empty one.
+    #[wasm_bindgen(catch, constructor, js_class = "Uint8Array")]
+    fn new_checked(buffer: &ArrayBuffer) -> Result<Uint8Array, JsValue>;
+}
+
+/// Panics in debug builds if `buffer` has been detached, which is what
+/// happens to the buffer of wasm's memory, and with it every view created by
+/// `view`, when the memory grows.
+#[inline]
+fn debug_assert_attached(buffer: &ArrayBuffer) {
+    if cfg!(debug_assertions) && Uint8Array::new_checked(buffer).is_err() {
+        panic!(
+            "typed array's buffer has been detached; views of wasm memory \
+             created with `view` are invalidated when the memory grows"
+        );
+    }
 }
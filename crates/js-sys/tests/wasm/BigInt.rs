@@ -0,0 +1,70 @@
+use js_sys::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn new() {
+    assert_eq!(BigInt::new(&JsValue::from(42)).unwrap(), BigInt::from(42));
+    assert_eq!(
+        BigInt::new(&JsValue::from("-12345678901234567890")).unwrap(),
+        BigInt::from(-12345678901234567890i128)
+    );
+    assert!(BigInt::new(&JsValue::from(1.5)).is_err());
+    assert!(BigInt::new(&JsValue::from("foo")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn as_int_n() {
+    let n = BigInt::from(u64::max_value());
+    assert_eq!(BigInt::as_int_n(64.0, &n), BigInt::from(-1));
+    assert_eq!(BigInt::as_uint_n(8.0, &n), BigInt::from(255));
+}
+
+#[wasm_bindgen_test]
+fn to_string() {
+    let n = BigInt::from(255);
+    assert_eq!(n.to_string(10).unwrap(), "255");
+    assert_eq!(n.to_string(16).unwrap(), "ff");
+    assert!(n.to_string(100).is_err());
+    assert_eq!(
+        format!("{:?}", BigInt::from(i128::min_value())),
+        i128::min_value().to_string()
+    );
+}
+
+#[wasm_bindgen_test]
+fn value_of() {
+    let n = BigInt::from(7);
+    assert_eq!(n.value_of(), n);
+}
+
+#[wasm_bindgen_test]
+fn arithmetic() {
+    let a = BigInt::from(u64::max_value());
+    let b = BigInt::from(2);
+    assert_eq!(&a + &b, BigInt::from(u128::from(u64::max_value()) + 2));
+    assert_eq!(&a - &b, BigInt::from(u64::max_value() - 2));
+    assert_eq!(&a * &b, BigInt::from(u128::from(u64::max_value()) * 2));
+    assert_eq!(&a / &b, BigInt::from(u64::max_value() / 2));
+    assert_eq!(&a % &b, BigInt::from(1));
+    assert_eq!(&a & &b, BigInt::from(2));
+    assert_eq!(&b | &BigInt::from(1), BigInt::from(3));
+    assert_eq!(&b ^ &BigInt::from(3), BigInt::from(1));
+    assert_eq!(&b << &BigInt::from(64), BigInt::from(2u128 << 64));
+    assert_eq!(&a >> &BigInt::from(32), BigInt::from(u32::max_value()));
+    assert_eq!(-b.clone(), BigInt::from(-2));
+    assert_eq!(
+        b.pow(&BigInt::from(100)),
+        BigInt::new(&JsValue::from("1267650600228229401496703205376")).unwrap()
+    );
+    assert_eq!(a + b, BigInt::from(u128::from(u64::max_value()) + 2));
+}
+
+#[wasm_bindgen_test]
+fn bigint_type() {
+    let n = JsValue::from(1u64);
+    assert!(n.is_bigint());
+    assert!(n.dyn_ref::<BigInt>().is_some());
+    assert!(JsValue::from(1).dyn_ref::<BigInt>().is_none());
+}
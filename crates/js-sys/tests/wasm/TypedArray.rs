@@ -14,6 +14,8 @@ macro_rules! each {
         $m!(Int32Array);
         $m!(Float32Array);
         $m!(Float64Array);
+        $m!(BigInt64Array);
+        $m!(BigUint64Array);
     };
 }
 
@@ -121,3 +123,64 @@ fn copy_to() {
         assert_eq!(*i, 5);
     }
 }
+
+#[wasm_bindgen_test]
+fn copy_from() {
+    let x = [1, 2, 3];
+    let array = Int32Array::new(&3.into());
+    array.copy_from(&x);
+    array.for_each(&mut |x, i, _| {
+        assert_eq!(x, (i + 1) as i32);
+    });
+}
+
+macro_rules! test_to_vec {
+    ($arr:ident) => {{
+        let arr = $arr::new(&4.into());
+        arr.fill(3 as _, 1, 3);
+        assert_eq!(arr.to_vec(), [0 as _, 3 as _, 3 as _, 0 as _]);
+    }};
+}
+#[wasm_bindgen_test]
+fn to_vec() {
+    each!(test_to_vec);
+}
+
+#[wasm_bindgen_test]
+fn from_slice() {
+    let x = [u64::max_value(), 2];
+    let array = BigUint64Array::from(&x[..]);
+    assert_eq!(array.length(), 2);
+    assert_eq!(array.to_vec(), x);
+
+    // The new array is a copy, not a view of the slice.
+    let mut y = [1.5f64, 2.5];
+    let array = Float64Array::from(&y[..]);
+    y[0] = 0.0;
+    assert_eq!(array.to_vec(), [1.5, 2.5]);
+}
+
+#[wasm_bindgen_test]
+fn view_is_invalidated_by_growth() {
+    let x = [1u8, 2, 3];
+    let array = unsafe { Uint8Array::view(&x) };
+    assert_eq!(array.to_vec(), x);
+
+    // Growing the memory detaches the buffer the view was created over.
+    let memory = wasm_bindgen::memory().unchecked_into::<WebAssembly::Memory>();
+    memory.grow(1);
+    assert_eq!(array.length(), 0);
+}
+
+// Using the view once it's been invalidated is only caught by a debug
+// assertion.
+#[cfg(debug_assertions)]
+#[wasm_bindgen_test]
+#[should_panic(expected = "typed array's buffer has been detached")]
+fn stale_view_panics_in_debug_builds() {
+    let x = [1u8, 2, 3];
+    let array = unsafe { Uint8Array::view(&x) };
+    let memory = wasm_bindgen::memory().unchecked_into::<WebAssembly::Memory>();
+    memory.grow(1);
+    array.to_vec();
+}
@@ -10,6 +10,7 @@ extern crate wasm_bindgen_test;
 pub mod Array;
 pub mod ArrayBuffer;
 pub mod ArrayIterator;
+pub mod BigInt;
 pub mod Boolean;
 pub mod DataView;
 pub mod Date;
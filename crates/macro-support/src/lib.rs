@@ -50,7 +50,16 @@ pub fn expand_class_marker(
     let opts: ClassMarker = syn::parse2(attr)?;
 
     let mut program = backend::ast::Program::default();
-    item.macro_parse(&mut program, (&opts.class, &opts.js_class))?;
+    match &opts {
+        ClassMarker::Class { class, js_class } => {
+            item.macro_parse(&mut program, (class, &js_class[..]))?
+        }
+        ClassMarker::Instantiated {
+            generics,
+            self_ty,
+            instantiations,
+        } => item.macro_parse(&mut program, (generics, self_ty, &instantiations[..]))?,
+    }
     parser::assert_all_attrs_checked(); // same as above
 
     // This is where things are slightly different, we are being expanded in the
@@ -90,16 +99,43 @@ pub fn expand_class_marker(
     Ok(tokens)
 }
 
-struct ClassMarker {
-    class: syn::Ident,
-    js_class: String,
+enum ClassMarker {
+    /// `Class = "JsClass"`, for methods of a plain impl.
+    Class { class: syn::Ident, js_class: String },
+    /// `impl<T> Class<T>, instantiate(...)`, for methods of a generic impl
+    /// which are exported once per instantiation.
+    Instantiated {
+        generics: syn::Generics,
+        self_ty: syn::Type,
+        instantiations: Vec<parser::Instantiation>,
+    },
 }
 
 impl Parse for ClassMarker {
     fn parse(input: ParseStream) -> SynResult<Self> {
+        if input.parse::<Option<Token![impl]>>()?.is_some() {
+            let generics = input.parse()?;
+            let self_ty = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "instantiate" {
+                return Err(syn::Error::new(ident.span(), "expected `instantiate`"));
+            }
+            let list;
+            parenthesized!(list in input);
+            let instantiations = list
+                .parse_terminated::<_, Token![,]>(parser::Instantiation::parse)?
+                .into_iter()
+                .collect();
+            return Ok(ClassMarker::Instantiated {
+                generics,
+                self_ty,
+                instantiations,
+            });
+        }
         let class = input.parse::<syn::Ident>()?;
         input.parse::<Token![=]>()?;
         let js_class = input.parse::<syn::LitStr>()?.value();
-        Ok(ClassMarker { class, js_class })
+        Ok(ClassMarker::Class { class, js_class })
     }
 }
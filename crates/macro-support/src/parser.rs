@@ -52,6 +52,14 @@ macro_rules! attrgen {
             (typescript_custom_section, TypescriptCustomSection(Span)),
             (start, Start(Span)),
             (skip, Skip(Span)),
+            (static_("static"), Static(Span)),
+            (iterator, Iterator(Span)),
+            (async_iterator, AsyncIterator(Span)),
+            (to_primitive, ToPrimitive(Span)),
+            (value_of, ValueOf(Span)),
+            (to_string, ToString(Span)),
+            (inspect, Inspect(Span)),
+            (instantiate, Instantiate(Span, Vec<Instantiation>)),
         }
     };
 }
@@ -258,6 +266,13 @@ impl Parse for BindgenAttr {
                 return Ok(BindgenAttr::$variant(attr_span, input.parse()?));
             });
 
+            (@parser $variant:ident(Span, Vec<Instantiation>)) => ({
+                let content;
+                parenthesized!(content in input);
+                let list = content.parse_terminated::<_, Token![,]>(Instantiation::parse)?;
+                return Ok(BindgenAttr::$variant(attr_span, list.into_iter().collect()))
+            });
+
             (@parser $variant:ident(Span, String, Span)) => ({
                 input.parse::<Token![=]>()?;
                 let (val, span) = match input.parse::<syn::LitStr>() {
@@ -280,6 +295,22 @@ impl Parse for BindgenAttr {
     }
 }
 
+/// One `JsName = Type<Args>` entry of `#[wasm_bindgen(instantiate(...))]`.
+#[cfg_attr(feature = "extra-traits", derive(Debug, PartialEq, Eq))]
+pub struct Instantiation {
+    pub js_name: Ident,
+    pub ty: syn::Type,
+}
+
+impl Parse for Instantiation {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let js_name = input.parse::<AnyIdent>()?.0;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        Ok(Instantiation { js_name, ty })
+    }
+}
+
 struct AnyIdent(Ident);
 
 impl Parse for AnyIdent {
@@ -305,21 +336,44 @@ trait ConvertToAst<Ctx> {
 }
 
 impl<'a> ConvertToAst<BindgenAttrs> for &'a mut syn::ItemStruct {
-    type Target = ast::Struct;
+    type Target = Vec<ast::Struct>;
 
     fn convert(self, attrs: BindgenAttrs) -> Result<Self::Target, Diagnostic> {
-        if self.generics.params.len() > 0 {
-            bail_span!(
-                self.generics,
-                "structs with #[wasm_bindgen] cannot have lifetime or \
-                 type parameters currently"
-            );
-        }
+        // Generic structs are exported as one class per instantiation listed
+        // in `instantiate(...)`, everything else is exported exactly once.
+        let instances = match attrs.instantiate() {
+            Some(list) => {
+                if let Some((_, span)) = attrs.js_name() {
+                    let msg = "cannot specify both `js_name` and `instantiate`";
+                    return Err(Diagnostic::span_error(span, msg));
+                }
+                let ident = &self.ident;
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+                let self_ty = parse_quote! { #ident #ty_generics };
+                list.iter()
+                    .map(|i| Instance::new(&self.generics, &self_ty, i))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            None => {
+                if self.generics.params.len() > 0 {
+                    bail_span!(
+                        self.generics,
+                        "structs with #[wasm_bindgen] cannot have lifetime or \
+                         type parameters currently"
+                    );
+                }
+                let js_name = attrs
+                    .js_name()
+                    .map(|s| s.0.to_string())
+                    .unwrap_or(self.ident.to_string());
+                vec![Instance {
+                    ty: ident_ty(self.ident.clone()),
+                    js_name,
+                    substitutions: Vec::new(),
+                }]
+            }
+        };
         let mut fields = Vec::new();
-        let js_name = attrs
-            .js_name()
-            .map(|s| s.0.to_string())
-            .unwrap_or(self.ident.to_string());
         for (i, field) in self.fields.iter_mut().enumerate() {
             match field.vis {
                 syn::Visibility::Public(..) => {}
@@ -338,28 +392,41 @@ impl<'a> ConvertToAst<BindgenAttrs> for &'a mut syn::ItemStruct {
             }
 
             let comments = extract_doc_comments(&field.attrs);
-            let getter = shared::struct_field_get(&js_name, &name_str);
-            let setter = shared::struct_field_set(&js_name, &name_str);
-
-            fields.push(ast::StructField {
-                name: member,
-                struct_name: self.ident.clone(),
-                readonly: attrs.readonly().is_some(),
-                ty: field.ty.clone(),
-                getter: Ident::new(&getter, Span::call_site()),
-                setter: Ident::new(&setter, Span::call_site()),
-                comments,
-            });
+            let readonly = attrs.readonly().is_some();
+            fields.push((name_str, member, readonly, field.ty.clone(), comments));
             attrs.check_used()?;
         }
         let comments: Vec<String> = extract_doc_comments(&self.attrs);
         attrs.check_used()?;
-        Ok(ast::Struct {
-            rust_name: self.ident.clone(),
-            js_name,
-            fields,
-            comments,
-        })
+        Ok(instances
+            .into_iter()
+            .map(|instance| {
+                let fields = fields
+                    .iter()
+                    .map(|(name_str, member, readonly, ty, comments)| {
+                        let getter = shared::struct_field_get(&instance.js_name, name_str);
+                        let setter = shared::struct_field_set(&instance.js_name, name_str);
+                        let mut ty = ty.clone();
+                        instance.substitute(&mut ty);
+                        ast::StructField {
+                            name: member.clone(),
+                            struct_name: instance.ty.clone(),
+                            readonly: *readonly,
+                            ty,
+                            getter: Ident::new(&getter, Span::call_site()),
+                            setter: Ident::new(&setter, Span::call_site()),
+                            comments: comments.clone(),
+                        }
+                    })
+                    .collect();
+                ast::Struct {
+                    rust_name: instance.ty,
+                    js_name: instance.js_name,
+                    fields,
+                    comments: comments.clone(),
+                }
+            })
+            .collect())
     }
 }
 
@@ -773,7 +840,7 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
             }
             syn::Item::Struct(mut s) => {
                 let opts = opts.unwrap_or_default();
-                program.structs.push((&mut s).convert(opts)?);
+                program.structs.extend((&mut s).convert(opts)?);
                 s.to_tokens(tokens);
             }
             syn::Item::Impl(mut i) => {
@@ -781,6 +848,11 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
                 (&mut i).macro_parse(program, opts)?;
                 i.to_tokens(tokens);
             }
+            syn::Item::Trait(mut t) => {
+                let opts = opts.unwrap_or_default();
+                (&mut t).macro_parse(program, opts)?;
+                t.to_tokens(tokens);
+            }
             syn::Item::ForeignMod(mut f) => {
                 let opts = match opts {
                     Some(opts) => opts,
@@ -806,7 +878,7 @@ impl<'a> MacroParse<(Option<BindgenAttrs>, &'a mut TokenStream)> for syn::Item {
                 bail_span!(
                     self,
                     "#[wasm_bindgen] can only be applied to a function, \
-                     struct, enum, impl, or extern block",
+                     struct, enum, impl, trait, or extern block",
                 );
             }
         }
@@ -836,25 +908,57 @@ impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemImpl {
         if let Some((_, path, _)) = &self.trait_ {
             bail_span!(path, "#[wasm_bindgen] trait impls are not supported");
         }
-        if self.generics.params.len() > 0 {
-            bail_span!(
-                self.generics,
-                "#[wasm_bindgen] generic impls aren't supported"
-            );
+
+        // Exported associated constants are published as static getters on
+        // the class, so generate a method for each of them which is then
+        // exported like any other.
+        let mut getters = Vec::new();
+        for item in self.items.iter_mut() {
+            if let syn::ImplItem::Const(c) = item {
+                getters.extend(const_getter(c)?);
+            }
         }
-        let name = match *self.self_ty {
-            syn::Type::Path(syn::TypePath {
-                qself: None,
-                ref path,
-            }) => extract_path_ident(path)?,
-            _ => bail_span!(
-                self.self_ty,
-                "unsupported self type in #[wasm_bindgen] impl"
-            ),
+        self.items.extend(getters);
+
+        // Generic impls pass their generics and instantiations on to the
+        // class marker so each method can be exported once per instantiation,
+        // with the concrete types of that instantiation. They're checked here
+        // first so errors are reported once for the whole impl.
+        let marker = if let Some(list) = opts.instantiate() {
+            for instantiation in list {
+                Instance::new(&self.generics, &self.self_ty, instantiation)?;
+            }
+            let generics = &self.generics;
+            let self_ty = &self.self_ty;
+            let js_names = list.iter().map(|i| &i.js_name);
+            let tys = list.iter().map(|i| &i.ty);
+            quote::quote! { (impl #generics #self_ty, instantiate(#(#js_names = #tys),*)) }
+        } else {
+            if self.generics.params.len() > 0 {
+                bail_span!(
+                    self.generics,
+                    "#[wasm_bindgen] generic impls aren't supported"
+                );
+            }
+            let name = match *self.self_ty {
+                syn::Type::Path(syn::TypePath {
+                    qself: None,
+                    ref path,
+                }) => extract_path_ident(path)?,
+                _ => bail_span!(
+                    self.self_ty,
+                    "unsupported self type in #[wasm_bindgen] impl"
+                ),
+            };
+            let js_class = opts
+                .js_class()
+                .map(|s| s.0.to_string())
+                .unwrap_or(name.to_string());
+            quote::quote! { (#name = #js_class) }
         };
         let mut errors = Vec::new();
         for item in self.items.iter_mut() {
-            if let Err(e) = prepare_for_impl_recursion(item, &name, &opts) {
+            if let Err(e) = prepare_for_impl_recursion(item, &marker) {
                 errors.push(e);
             }
         }
@@ -864,27 +968,13 @@ impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemImpl {
     }
 }
 
-// Prepare for recursion into an `impl` block. Here we want to attach an
-// internal attribute, `__wasm_bindgen_class_marker`, with any metadata we need
-// to pass from the impl to the impl item. Recursive macro expansion will then
-// expand the `__wasm_bindgen_class_marker` attribute.
-//
-// Note that we currently do this because inner items may have things like cfgs
-// on them, so we want to expand the impl first, let the insides get cfg'd, and
-// then go for the rest.
-fn prepare_for_impl_recursion(
+fn impl_item_method(
     item: &mut syn::ImplItem,
-    class: &Ident,
-    impl_opts: &BindgenAttrs,
-) -> Result<(), Diagnostic> {
-    let method = match item {
-        syn::ImplItem::Method(m) => m,
-        syn::ImplItem::Const(_) => {
-            bail_span!(
-                &*item,
-                "const definitions aren't supported with #[wasm_bindgen]"
-            );
-        }
+) -> Result<Option<&mut syn::ImplItemMethod>, Diagnostic> {
+    match item {
+        syn::ImplItem::Method(m) => Ok(Some(m)),
+        // Handled by `const_getter`
+        syn::ImplItem::Const(_) => Ok(None),
         syn::ImplItem::Type(_) => bail_span!(
             &*item,
             "type definitions in impls aren't supported with #[wasm_bindgen]"
@@ -901,12 +991,25 @@ fn prepare_for_impl_recursion(
             bail_span!(&*item, "macros in impls aren't supported");
         }
         syn::ImplItem::Verbatim(_) => panic!("unparsed impl item?"),
-    };
+    }
+}
 
-    let js_class = impl_opts
-        .js_class()
-        .map(|s| s.0.to_string())
-        .unwrap_or(class.to_string());
+// Prepare for recursion into an `impl` block. Here we want to attach an
+// internal attribute, `__wasm_bindgen_class_marker`, with any metadata we need
+// to pass from the impl to the impl item. Recursive macro expansion will then
+// expand the `__wasm_bindgen_class_marker` attribute.
+//
+// Note that we currently do this because inner items may have things like cfgs
+// on them, so we want to expand the impl first, let the insides get cfg'd, and
+// then go for the rest.
+fn prepare_for_impl_recursion(
+    item: &mut syn::ImplItem,
+    marker: &TokenStream,
+) -> Result<(), Diagnostic> {
+    let method = match impl_item_method(item)? {
+        Some(method) => method,
+        None => return Ok(()),
+    };
 
     method.attrs.insert(
         0,
@@ -915,7 +1018,7 @@ fn prepare_for_impl_recursion(
             style: syn::AttrStyle::Outer,
             bracket_token: Default::default(),
             path: syn::parse_quote! { wasm_bindgen::prelude::__wasm_bindgen_class_marker },
-            tts: quote::quote! { (#class = #js_class) }.into(),
+            tts: marker.clone(),
         },
     );
 
@@ -928,56 +1031,502 @@ impl<'a, 'b> MacroParse<(&'a Ident, &'a str)> for &'b mut syn::ImplItemMethod {
         program: &mut ast::Program,
         (class, js_class): (&'a Ident, &'a str),
     ) -> Result<(), Diagnostic> {
-        match self.vis {
-            syn::Visibility::Public(_) => {}
-            _ => return Ok(()),
+        if !is_exported_method(self)? {
+            return Ok(());
         }
-        if self.defaultness.is_some() {
-            panic!("default methods are not supported");
+        let opts = BindgenAttrs::find(&mut self.attrs)?;
+        let class_ty = ident_ty(class.clone());
+        let export = method_export(self, &opts, &class_ty, js_class, Some(class), &[])?;
+        program.exports.push(export);
+        opts.check_used()?;
+        Ok(())
+    }
+}
+
+impl<'a, 'b> MacroParse<(&'a syn::Generics, &'a syn::Type, &'a [Instantiation])>
+    for &'b mut syn::ImplItemMethod
+{
+    fn macro_parse(
+        self,
+        program: &mut ast::Program,
+        (generics, self_ty, list): (&'a syn::Generics, &'a syn::Type, &'a [Instantiation]),
+    ) -> Result<(), Diagnostic> {
+        if !is_exported_method(self)? {
+            return Ok(());
+        }
+        let opts = BindgenAttrs::find(&mut self.attrs)?;
+        for instantiation in list {
+            let instance = Instance::new(generics, self_ty, instantiation)?;
+            let export = method_export(
+                self,
+                &opts,
+                &instance.ty,
+                &instance.js_name,
+                None,
+                &instance.substitutions,
+            )?;
+            program.exports.push(export);
+        }
+        opts.check_used()?;
+        Ok(())
+    }
+}
+
+/// Generates a static getter for an exported associated constant.
+fn const_getter(c: &mut syn::ImplItemConst) -> Result<Option<syn::ImplItem>, Diagnostic> {
+    let opts = BindgenAttrs::find(&mut c.attrs)?;
+    match c.vis {
+        syn::Visibility::Public(_) => {}
+        _ => {
+            opts.check_used()?;
+            return Ok(None);
+        }
+    }
+    let ident = &c.ident;
+    let ty = &c.ty;
+    let attrs = &c.attrs;
+    let js_name = opts
+        .js_name()
+        .map(|s| s.0.to_string())
+        .unwrap_or(ident.to_string());
+    let getter = Ident::new(&format!("__wbg_const_{}", ident), ident.span());
+    opts.check_used()?;
+    Ok(Some(parse_quote! {
+        #(#attrs)*
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        #[wasm_bindgen(js_name = #js_name, getter, static)]
+        pub fn #getter() -> #ty {
+            Self::#ident
+        }
+    }))
+}
+
+/// Checks whether a method in an `impl` block should be exported, erroring out
+/// on methods which can't be.
+fn is_exported_method(method: &syn::ImplItemMethod) -> Result<bool, Diagnostic> {
+    match method.vis {
+        syn::Visibility::Public(_) => {}
+        _ => return Ok(false),
+    }
+    if method.defaultness.is_some() {
+        panic!("default methods are not supported");
+    }
+    if method.sig.constness.is_some() {
+        bail_span!(
+            method.sig.constness,
+            "can only #[wasm_bindgen] non-const functions",
+        );
+    }
+    if method.sig.unsafety.is_some() {
+        bail_span!(method.sig.unsafety, "can only bindgen safe functions",);
+    }
+    Ok(true)
+}
+
+fn method_export(
+    method: &syn::ImplItemMethod,
+    opts: &BindgenAttrs,
+    class: &syn::Type,
+    js_class: &str,
+    self_ty: Option<&Ident>,
+    substitutions: &[(Ident, syn::Type)],
+) -> Result<ast::Export, Diagnostic> {
+    let comments = extract_doc_comments(&method.attrs);
+    let mut decl = method.sig.decl.clone();
+    syn::visit_mut::VisitMut::visit_fn_decl_mut(&mut Substitute(substitutions), &mut decl);
+    let (function, method_self) = function_from_decl(
+        &method.sig.ident,
+        opts,
+        Box::new(decl),
+        method.attrs.clone(),
+        method.vis.clone(),
+        true,
+        self_ty,
+    )?;
+    if let Some(span) = opts.static_() {
+        if method_self.is_some() {
+            let msg = "static methods cannot take `self`";
+            return Err(Diagnostic::span_error(*span, msg));
+        }
+    } else if method_self.is_none() && (opts.getter().is_some() || opts.setter().is_some()) {
+        bail_span!(
+            method.sig.ident,
+            "getters and setters which don't take `self` must be marked `static`",
+        );
+    }
+    let hook = class_hook(opts)?;
+    if let Some((span, name, _)) = hook {
+        if method_self.is_none() {
+            let msg = format!("`{}` methods must take `self`", name);
+            return Err(Diagnostic::span_error(span, msg));
         }
-        if self.sig.constness.is_some() {
+    }
+    let method_kind = if opts.constructor().is_some() {
+        ast::MethodKind::Constructor
+    } else {
+        let is_static = method_self.is_none();
+        let kind = match hook {
+            Some((_, _, hook)) => ast::OperationKind::Hook(hook),
+            None => operation_kind(opts)?,
+        };
+        ast::MethodKind::Operation(ast::Operation { is_static, kind })
+    };
+    Ok(ast::Export {
+        comments,
+        function,
+        js_class: Some(js_class.to_string()),
+        method_kind,
+        method_self,
+        rust_class: Some(class.clone()),
+        rust_name: method.sig.ident.clone(),
+        start: false,
+    })
+}
+
+/// One concrete instantiation of a generic struct or impl, exported to JS as
+/// its own class.
+struct Instance {
+    ty: syn::Type,
+    js_name: String,
+    /// Concrete types for the generic parameters of the struct or impl, as
+    /// well as for `Self`.
+    substitutions: Vec<(Ident, syn::Type)>,
+}
+
+impl Instance {
+    fn new(
+        generics: &syn::Generics,
+        self_ty: &syn::Type,
+        instantiation: &Instantiation,
+    ) -> Result<Instance, Diagnostic> {
+        if let Some(lifetime) = generics.lifetimes().next() {
             bail_span!(
-                self.sig.constness,
-                "can only #[wasm_bindgen] non-const functions",
+                lifetime,
+                "#[wasm_bindgen] instantiated types cannot have lifetime parameters"
             );
         }
-        if self.sig.unsafety.is_some() {
-            bail_span!(self.sig.unsafety, "can only bindgen safe functions",);
+        let instance = &instantiation.ty;
+        let (self_args, instance_args) = match (type_args(self_ty), type_args(instance)) {
+            (Some((a, a_args)), Some((b, b_args))) if a == b && a_args.len() == b_args.len() => {
+                (a_args, b_args)
+            }
+            _ => bail_span!(
+                instance,
+                "not an instantiation of the type this attribute is applied to"
+            ),
+        };
+
+        let mut substitutions = vec![(Ident::new("Self", Span::call_site()), instance.clone())];
+        for param in generics.type_params() {
+            let position = self_args.iter().position(|arg| match arg {
+                syn::Type::Path(syn::TypePath { qself: None, path }) => {
+                    path.is_ident(param.ident.clone())
+                }
+                _ => false,
+            });
+            match position {
+                Some(i) => substitutions.push((param.ident.clone(), instance_args[i].clone())),
+                None => bail_span!(
+                    param,
+                    "type parameter `{}` isn't determined by `instantiate`",
+                    param.ident
+                ),
+            }
         }
+        Ok(Instance {
+            ty: instance.clone(),
+            js_name: instantiation.js_name.to_string(),
+            substitutions,
+        })
+    }
 
-        let opts = BindgenAttrs::find(&mut self.attrs)?;
-        let comments = extract_doc_comments(&self.attrs);
-        let (function, method_self) = function_from_decl(
-            &self.sig.ident,
-            &opts,
-            Box::new(self.sig.decl.clone()),
-            self.attrs.clone(),
-            self.vis.clone(),
-            true,
-            Some(class),
-        )?;
-        let method_kind = if opts.constructor().is_some() {
-            ast::MethodKind::Constructor
-        } else {
-            let is_static = method_self.is_none();
-            let kind = operation_kind(&opts)?;
-            ast::MethodKind::Operation(ast::Operation { is_static, kind })
+    fn substitute(&self, ty: &mut syn::Type) {
+        syn::visit_mut::VisitMut::visit_type_mut(&mut Substitute(&self.substitutions), ty);
+    }
+}
+
+/// Returns the name and type arguments of a type like `Matrix<f32>`.
+fn type_args(ty: &syn::Type) -> Option<(&Ident, Vec<&syn::Type>)> {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?.into_value();
+    let args = match &segment.arguments {
+        syn::PathArguments::None => Vec::new(),
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        syn::PathArguments::Parenthesized(_) => return None,
+    };
+    Some((&segment.ident, args))
+}
+
+/// Replaces generic parameters with the concrete types of an `Instance`.
+struct Substitute<'a>(&'a [(Ident, syn::Type)]);
+
+impl<'a> syn::visit_mut::VisitMut for Substitute<'a> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+            let replacement = self
+                .0
+                .iter()
+                .find(|(param, _)| path.is_ident(param.clone()))
+                .map(|(_, concrete)| concrete.clone());
+            if let Some(concrete) = replacement {
+                *ty = concrete;
+                return;
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
+
+impl<'a> MacroParse<BindgenAttrs> for &'a mut syn::ItemTrait {
+    fn macro_parse(self, program: &mut ast::Program, opts: BindgenAttrs) -> Result<(), Diagnostic> {
+        if self.unsafety.is_some() {
+            bail_span!(
+                self.unsafety,
+                "#[wasm_bindgen] unsafe traits are not supported"
+            );
+        }
+        if self.auto_token.is_some() {
+            bail_span!(
+                self.auto_token,
+                "#[wasm_bindgen] auto traits are not supported"
+            );
+        }
+        if self.generics.params.len() > 0 {
+            bail_span!(
+                self.generics,
+                "#[wasm_bindgen] generic traits aren't supported"
+            );
+        }
+        if self.supertraits.len() > 0 {
+            bail_span!(
+                self.supertraits,
+                "#[wasm_bindgen] traits with supertraits aren't supported"
+            );
+        }
+        assert_not_variadic(&opts)?;
+
+        let name = &self.ident;
+        let js_name = opts
+            .js_name()
+            .map(|s| s.0.to_string())
+            .unwrap_or(name.to_string());
+        let adapter = Ident::new(&format!("Js{}", name), name.span());
+        let class: syn::Type = parse_quote! {
+            wasm_bindgen::__rt::std::boxed::Box<dyn #name>
         };
-        program.exports.push(ast::Export {
-            comments,
-            function,
-            js_class: Some(js_class.to_string()),
-            method_kind,
-            method_self,
-            rust_class: Some(class.clone()),
-            rust_name: self.sig.ident.clone(),
-            start: false,
+
+        let mut errors = Vec::new();
+        let mut methods = Vec::new();
+        for item in self.items.iter_mut() {
+            let method = match item {
+                syn::TraitItem::Method(m) => m,
+                syn::TraitItem::Const(_) => {
+                    errors.push(err_span!(
+                        &*item,
+                        "const definitions aren't supported with #[wasm_bindgen]"
+                    ));
+                    continue;
+                }
+                syn::TraitItem::Type(_) => {
+                    errors.push(err_span!(
+                        &*item,
+                        "associated types in traits aren't supported with #[wasm_bindgen]"
+                    ));
+                    continue;
+                }
+                syn::TraitItem::Macro(_) => {
+                    errors.push(err_span!(&*item, "macros in traits aren't supported"));
+                    continue;
+                }
+                syn::TraitItem::Verbatim(_) => panic!("unparsed trait item?"),
+            };
+            match trait_method(method, program, &class, &js_name, &adapter, &self.vis) {
+                Ok(m) => methods.push(m),
+                Err(e) => errors.push(e),
+            }
+        }
+        Diagnostic::from_vec(errors)?;
+
+        program.imports.push(ast::Import {
+            module: ast::ImportModule::None,
+            js_namespace: None,
+            kind: ast::ImportKind::Type(ast::ImportType {
+                vis: self.vis.clone(),
+                rust_name: adapter.clone(),
+                js_name: "Object".to_string(),
+                attrs: Vec::new(),
+                doc_comment: Some(format!(
+                    "A JS object implementing `{}` by calling its methods \
+                     structurally.",
+                    name
+                )),
+                instanceof_shim: format!("__wbg_instanceof_{}_{}", adapter, ShortHash(&adapter)),
+                is_type_of: None,
+                extends: Vec::new(),
+                vendor_prefixes: Vec::new(),
+            }),
+        });
+        program.traits.push(ast::Trait {
+            rust_name: name.clone(),
+            js_name,
+            adapter,
+            methods,
+            comments: extract_doc_comments(&self.attrs),
         });
         opts.check_used()?;
         Ok(())
     }
 }
 
+// Each method of an exported trait turns into two things: an export which
+// calls the method on a trait object owned by JS, and a structural import
+// which the `Js*` adapter uses to implement the method on top of an arbitrary
+// JS object.
+fn trait_method(
+    method: &mut syn::TraitItemMethod,
+    program: &mut ast::Program,
+    class: &syn::Type,
+    js_class: &str,
+    adapter: &Ident,
+    vis: &syn::Visibility,
+) -> Result<ast::TraitMethod, Diagnostic> {
+    if method.sig.constness.is_some() {
+        bail_span!(
+            method.sig.constness,
+            "can only #[wasm_bindgen] non-const functions",
+        );
+    }
+    if method.sig.unsafety.is_some() {
+        bail_span!(method.sig.unsafety, "can only bindgen safe functions",);
+    }
+
+    let opts = BindgenAttrs::find(&mut method.attrs)?;
+    assert_not_variadic(&opts)?;
+    let comments = extract_doc_comments(&method.attrs);
+
+    // Arguments are forwarded by name from the adapter's trait impl to
+    // its imports, so give every argument a plain identifier.
+    let mut decl = method.sig.decl.clone();
+    for (i, arg) in decl.inputs.iter_mut().enumerate() {
+        if let syn::FnArg::Captured(c) = arg {
+            let ident = match &c.pat {
+                syn::Pat::Ident(p) => p.ident.clone(),
+                _ => Ident::new(&format!("arg{}", i), Span::call_site()),
+            };
+            c.pat = syn::Pat::Ident(syn::PatIdent {
+                by_ref: None,
+                mutability: None,
+                ident,
+                subpat: None,
+            });
+        }
+    }
+    let (function, method_self) = function_from_decl(
+        &method.sig.ident,
+        &opts,
+        Box::new(decl),
+        method.attrs.clone(),
+        vis.clone(),
+        true,
+        None,
+    )?;
+    let method_self = match method_self {
+        Some(ast::MethodSelf::ByValue) | None => bail_span!(
+            method.sig,
+            "methods of #[wasm_bindgen] traits must take `&self` or `&mut self`"
+        ),
+        Some(method_self) => method_self,
+    };
+    let kind = operation_kind(&opts)?;
+
+    program.exports.push(ast::Export {
+        comments,
+        function: function.clone(),
+        js_class: Some(js_class.to_string()),
+        method_kind: ast::MethodKind::Operation(ast::Operation {
+            is_static: false,
+            kind: kind.clone(),
+        }),
+        method_self: Some(method_self.clone()),
+        rust_class: Some(class.clone()),
+        rust_name: method.sig.ident.clone(),
+        start: false,
+    });
+
+    let shim = {
+        let data = (
+            (1, adapter.to_string()),
+            &method.sig.ident,
+            &ast::ImportModule::None,
+        );
+        format!(
+            "__wbg_{}_{}",
+            function
+                .name
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect::<String>(),
+            ShortHash(data)
+        )
+    };
+    let mut import = function.clone();
+    import.rust_attrs = Vec::new();
+    import.arguments.insert(
+        0,
+        syn::ArgCaptured {
+            pat: syn::Pat::Ident(syn::PatIdent {
+                by_ref: None,
+                mutability: None,
+                ident: Ident::new("this", Span::call_site()),
+                subpat: None,
+            }),
+            colon_token: Default::default(),
+            ty: parse_quote! { &#adapter },
+        },
+    );
+    program.imports.push(ast::Import {
+        module: ast::ImportModule::None,
+        js_namespace: None,
+        kind: ast::ImportKind::Function(ast::ImportFunction {
+            function: import,
+            rust_name: method.sig.ident.clone(),
+            js_ret: function.ret.clone(),
+            catch: false,
+            variadic: false,
+            structural: true,
+            kind: ast::ImportFunctionKind::Method {
+                class: "Object".to_string(),
+                ty: ident_ty(adapter.clone()),
+                kind: ast::MethodKind::Operation(ast::Operation {
+                    is_static: false,
+                    kind,
+                }),
+            },
+            shim: Ident::new(&shim, Span::call_site()),
+            doc_comment: None,
+        }),
+    });
+    opts.check_used()?;
+
+    Ok(ast::TraitMethod {
+        rust_name: method.sig.ident.clone(),
+        method_self,
+        arguments: function.arguments,
+        ret: function.ret,
+    })
+}
+
 impl MacroParse<()> for syn::ItemEnum {
     fn macro_parse(self, program: &mut ast::Program, (): ()) -> Result<(), Diagnostic> {
         match self.vis {
@@ -1305,6 +1854,38 @@ pub fn assert_all_attrs_checked() {
     })
 }
 
+/// Returns the well-known method or symbol an exported method is installed as
+/// in JS, if any, along with the attribute that requested it.
+fn class_hook(
+    opts: &BindgenAttrs,
+) -> Result<Option<(Span, &'static str, ast::ClassHook)>, Diagnostic> {
+    let hooks = [
+        (opts.iterator(), "iterator", ast::ClassHook::Iterator),
+        (
+            opts.async_iterator(),
+            "async_iterator",
+            ast::ClassHook::AsyncIterator,
+        ),
+        (
+            opts.to_primitive(),
+            "to_primitive",
+            ast::ClassHook::ToPrimitive,
+        ),
+        (opts.value_of(), "value_of", ast::ClassHook::ValueOf),
+        (opts.to_string(), "to_string", ast::ClassHook::ToString),
+        (opts.inspect(), "inspect", ast::ClassHook::Inspect),
+    ];
+    let mut present = hooks
+        .iter()
+        .filter_map(|(span, name, hook)| span.map(|span| (*span, *name, *hook)));
+    let first = present.next();
+    if let (Some((_, first, _)), Some((span, second, _))) = (first, present.next()) {
+        let msg = format!("cannot specify both `{}` and `{}`", first, second);
+        return Err(Diagnostic::span_error(span, msg));
+    }
+    Ok(first)
+}
+
 fn operation_kind(opts: &BindgenAttrs) -> Result<ast::OperationKind, Diagnostic> {
     let mut operation_kind = ast::OperationKind::Regular;
     if let Some(g) = opts.getter() {
@@ -0,0 +1,16 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(instantiate(AU32 = A<u32>))]
+pub struct A<T> {
+    x: T,
+}
+
+#[wasm_bindgen(instantiate(AU32 = A<u32>))]
+impl<T> A<T> {
+    #[cfg(any())]
+    pub unsafe fn missing() {}
+}
+
+fn main() {
+    A::<u32>::missing();
+}
@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(iterator)]
+    pub fn iter() -> JsValue {
+        JsValue::NULL
+    }
+
+    #[wasm_bindgen(to_string)]
+    pub fn to_string() -> String {
+        String::new()
+    }
+
+    #[wasm_bindgen(iterator, to_string)]
+    pub fn both(&self) -> JsValue {
+        JsValue::NULL
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    type B;
+
+    #[wasm_bindgen(method, iterator)]
+    fn iter(this: &B) -> JsValue;
+}
+
+fn main() {}
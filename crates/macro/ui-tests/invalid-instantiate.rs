@@ -0,0 +1,31 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(instantiate(AU32 = A<'static, u32>))]
+pub struct A<'a, T> {
+    x: &'a T,
+}
+
+#[wasm_bindgen(instantiate(BU32 = Vec<u32>))]
+pub struct B<T> {
+    x: T,
+}
+
+#[wasm_bindgen(instantiate(CU32 = C<u32>), js_name = Foo)]
+pub struct C<T> {
+    x: T,
+}
+
+pub struct D<T>(T);
+
+#[wasm_bindgen(instantiate(DU32 = D<u32>))]
+impl<T, U> D<T> {
+}
+
+pub struct E<T>(T);
+
+impl<T> E<T> {
+    #[wasm_bindgen::prelude::__wasm_bindgen_class_marker(impl<T> E<T>, instanciate(EU32 = E<u32>))]
+    pub fn f() {}
+}
+
+fn main() {}
@@ -27,7 +27,6 @@ macro_rules! x { () => () }
 
 #[wasm_bindgen]
 impl A {
-    const X: u32 = 3;
     type Y = u32;
     x!();
 
@@ -0,0 +1,25 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct A;
+
+#[wasm_bindgen]
+impl A {
+    #[wasm_bindgen(getter, static)]
+    pub fn a(&self) -> u32 {
+        1
+    }
+
+    #[wasm_bindgen(static)]
+    pub fn b(self) {}
+
+    #[wasm_bindgen(getter)]
+    pub fn c() -> u32 {
+        3
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_c(_: u32) {}
+}
+
+fn main() {}
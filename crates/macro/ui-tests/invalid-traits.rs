@@ -0,0 +1,20 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub unsafe trait A {}
+
+#[wasm_bindgen]
+pub trait B<T> {}
+
+#[wasm_bindgen]
+pub trait C: Clone {}
+
+#[wasm_bindgen]
+pub trait D {
+    const X: u32;
+    type Y;
+    fn new() -> u32;
+    fn consume(self);
+}
+
+fn main() {}
@@ -71,6 +71,16 @@ macro_rules! shared_api {
             IndexingGetter,
             IndexingSetter,
             IndexingDeleter,
+            Hook(ClassHook),
+        }
+
+        enum ClassHook {
+            Iterator,
+            AsyncIterator,
+            ToPrimitive,
+            ValueOf,
+            ToString,
+            Inspect,
         }
 
         struct ImportStatic<'a> {
@@ -138,6 +148,13 @@ pub fn new_function(struct_name: &str) -> String {
     return name;
 }
 
+pub fn unwrap_function(struct_name: &str) -> String {
+    let mut name = format!("__wbg_");
+    name.extend(struct_name.chars().flat_map(|s| s.to_lowercase()));
+    name.push_str("_unwrap");
+    return name;
+}
+
 pub fn free_function(struct_name: &str) -> String {
     let mut name = format!("__wbg_");
     name.extend(struct_name.chars().flat_map(|s| s.to_lowercase()));
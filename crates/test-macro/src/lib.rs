@@ -16,9 +16,22 @@ pub fn wasm_bindgen_test(
 ) -> proc_macro::TokenStream {
     let mut attr = attr.into_iter();
     let mut r#async = false;
+    let mut timeout_ms = None;
     while let Some(token) = attr.next() {
         match &token {
             proc_macro::TokenTree::Ident(i) if i.to_string() == "async" => r#async = true,
+            proc_macro::TokenTree::Ident(i) if i.to_string() == "timeout_ms" => {
+                match &attr.next() {
+                    Some(proc_macro::TokenTree::Punct(eq)) if eq.as_char() == '=' => {}
+                    _ => panic!("malformed `#[wasm_bindgen_test]` attribute"),
+                }
+                match attr.next() {
+                    Some(ms @ proc_macro::TokenTree::Literal(_)) => {
+                        timeout_ms = Some(TokenStream::from(proc_macro::TokenStream::from(ms)));
+                    }
+                    _ => panic!("malformed `#[wasm_bindgen_test]` attribute"),
+                }
+            }
             _ => panic!("malformed `#[wasm_bindgen_test]` attribute"),
         }
         match &attr.next() {
@@ -28,54 +41,254 @@ pub fn wasm_bindgen_test(
         }
     }
 
-    let mut body = TokenStream::from(body).into_iter();
+    let test = TestFn::parse(body);
+    let ident = &test.ident;
+    let should_panic = match &test.should_panic {
+        Some(tokens) => tokens.clone(),
+        None => quote!(::wasm_bindgen_test::__rt::ShouldPanic::No),
+    };
+    let ignore = test.ignore;
+    let test_name = test.name();
 
-    // Skip over other attributes to `fn #ident ...`, and extract `#ident`
-    let mut leading_tokens = Vec::new();
-    while let Some(token) = body.next() {
-        leading_tokens.push(token.clone());
-        if let TokenTree::Ident(token) = token {
-            if token == "fn" {
-                break;
-            }
-        }
-    }
-    let ident = match body.next() {
-        Some(TokenTree::Ident(token)) => token,
-        _ => panic!("expected a function name"),
+    let test_body = if r#async {
+        let timeout_ms = match timeout_ms {
+            Some(ms) => quote!(Some(#ms)),
+            None => quote!(None),
+        };
+        quote! { cx.execute_async(#test_name, #ident, #should_panic, #ignore, #timeout_ms); }
+    } else if timeout_ms.is_some() {
+        panic!("`timeout_ms` is only supported for `async` tests")
+    } else {
+        quote! { cx.execute_sync(#test_name, #ident, #should_panic, #ignore); }
     };
 
-    let mut tokens = Vec::<TokenTree>::new();
+    test.expand("__wbg_test", test_body)
+}
 
-    let test_body = if r#async {
-        quote! { cx.execute_async(test_name, #ident); }
+#[proc_macro_attribute]
+pub fn wasm_bindgen_bench(
+    attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        panic!("`#[wasm_bindgen_bench]` doesn't take any arguments");
+    }
+
+    let test = TestFn::parse(body);
+    if test.should_panic.is_some() {
+        panic!("`#[should_panic]` isn't supported for benchmarks");
+    }
+    let ident = &test.ident;
+    let ignore = test.ignore;
+    let test_name = test.name();
+    let test_body = quote! { cx.execute_bench(#test_name, #ident, #ignore); };
+
+    test.expand("__wbg_test", test_body)
+}
+
+#[proc_macro_attribute]
+pub fn wasm_bindgen_test_setup(
+    attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    hook("wasm_bindgen_test_setup", quote!(Setup), attr, body)
+}
+
+#[proc_macro_attribute]
+pub fn wasm_bindgen_test_teardown(
+    attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    hook("wasm_bindgen_test_teardown", quote!(Teardown), attr, body)
+}
+
+/// Expands a setup or teardown hook, which is registered with the test harness
+/// for all tests of the module it's defined in.
+fn hook(
+    macro_name: &str,
+    kind: TokenStream,
+    attr: proc_macro::TokenStream,
+    body: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut attr = attr.into_iter();
+    let r#async = match attr.next() {
+        None => false,
+        Some(proc_macro::TokenTree::Ident(i)) if i.to_string() == "async" => true,
+        Some(_) => panic!("malformed `#[{}]` attribute", macro_name),
+    };
+    if attr.next().is_some() {
+        panic!("malformed `#[{}]` attribute", macro_name);
+    }
+
+    let test = TestFn::parse(body);
+    if test.ignore || test.should_panic.is_some() {
+        panic!("`#[ignore]` and `#[should_panic]` aren't supported for hooks");
+    }
+    let ident = &test.ident;
+    let register = if r#async {
+        quote!(register_hook_async)
     } else {
-        quote! { cx.execute_sync(test_name, #ident); }
+        quote!(register_hook_sync)
     };
+    let test_body = quote! {
+        cx.#register(module_path!(), ::wasm_bindgen_test::__rt::HookKind::#kind, #ident);
+    };
+
+    test.expand(&format!("__wbg_test_{}", macro_name), test_body)
+}
 
-    // We generate a `#[no_mangle]` with a known prefix so the test harness can
-    // later slurp up all of these functions and pass them as arguments to the
-    // main test harness. This is the entry point for all tests.
-    let name = format!(
-        "__wbg_test_{}_{}",
-        ident,
-        CNT.fetch_add(1, Ordering::SeqCst)
-    );
-    let name = Ident::new(&name, Span::call_site());
-    tokens.extend(
-        (quote! {
-            #[no_mangle]
-            pub extern "C" fn #name(cx: &::wasm_bindgen_test::__rt::Context) {
-                let test_name = concat!(module_path!(), "::", stringify!(#ident));
-                #test_body
+/// A function annotated with `#[wasm_bindgen_test]`, `#[wasm_bindgen_bench]`
+/// or one of the hook attributes.
+struct TestFn {
+    /// Everything up to and including `fn`, minus the attributes handled by
+    /// the test harness.
+    leading_tokens: Vec<TokenTree>,
+    ident: Ident,
+    /// Everything after the name of the function.
+    rest: Vec<TokenTree>,
+    ignore: bool,
+    should_panic: Option<TokenStream>,
+}
+
+impl TestFn {
+    fn parse(body: proc_macro::TokenStream) -> TestFn {
+        let mut body = TokenStream::from(body).into_iter().peekable();
+
+        // Skip over other attributes to `fn #ident ...`, and extract `#ident`.
+        // Along the way `#[ignore]` and `#[should_panic]` are removed as
+        // they're implemented by the test harness rather than by the compiler.
+        let mut leading_tokens = Vec::new();
+        let mut ignore = false;
+        let mut should_panic = None;
+        while let Some(token) = body.next() {
+            if let TokenTree::Punct(p) = &token {
+                if p.as_char() == '#' {
+                    let attr = match body.peek() {
+                        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
+                            test_attribute(g.stream())
+                        }
+                        _ => None,
+                    };
+                    match attr {
+                        Some(TestAttribute::Ignore) => ignore = true,
+                        Some(TestAttribute::ShouldPanic(tokens)) => should_panic = Some(tokens),
+                        None => {
+                            leading_tokens.push(token);
+                            continue;
+                        }
+                    }
+                    body.next();
+                    continue;
+                }
             }
-        })
-        .into_iter(),
-    );
+            leading_tokens.push(token.clone());
+            if let TokenTree::Ident(token) = token {
+                if token == "fn" {
+                    break;
+                }
+            }
+        }
+        let ident = match body.next() {
+            Some(TokenTree::Ident(token)) => token,
+            _ => panic!("expected a function name"),
+        };
 
-    tokens.extend(leading_tokens);
-    tokens.push(ident.into());
-    tokens.extend(body);
+        TestFn {
+            leading_tokens,
+            ident,
+            rest: body.collect(),
+            ignore,
+            should_panic,
+        }
+    }
+
+    /// The full name of the test, as reported by the test harness.
+    fn name(&self) -> TokenStream {
+        let ident = &self.ident;
+        quote!(concat!(module_path!(), "::", stringify!(#ident)))
+    }
 
-    tokens.into_iter().collect::<TokenStream>().into()
+    /// Generates the function itself along with its entry point for the test
+    /// harness, named with `prefix`, which runs `test_body` with `cx` in scope.
+    fn expand(self, prefix: &str, test_body: TokenStream) -> proc_macro::TokenStream {
+        let mut tokens = Vec::<TokenTree>::new();
+
+        // We generate a `#[no_mangle]` with a known prefix so the test harness
+        // can later slurp up all of these functions and pass them as arguments
+        // to the main test harness. This is the entry point for all tests, and
+        // hooks use it to register themselves too.
+        let ident = self.ident;
+        let name = format!(
+            "{}_{}_{}",
+            prefix,
+            ident,
+            CNT.fetch_add(1, Ordering::SeqCst)
+        );
+        let name = Ident::new(&name, Span::call_site());
+        tokens.extend(
+            (quote! {
+                #[no_mangle]
+                pub extern "C" fn #name(cx: &::wasm_bindgen_test::__rt::Context) {
+                    #test_body
+                }
+            })
+            .into_iter(),
+        );
+
+        tokens.extend(self.leading_tokens);
+        tokens.push(ident.into());
+        tokens.extend(self.rest);
+
+        tokens.into_iter().collect::<TokenStream>().into()
+    }
+}
+
+enum TestAttribute {
+    Ignore,
+    ShouldPanic(TokenStream),
+}
+
+/// Parses the contents of an attribute on a test function, returning what it
+/// means if it's one of the attributes handled by the test harness.
+///
+/// Like libtest this accepts `#[ignore]`, `#[ignore = "reason"]`,
+/// `#[should_panic]`, `#[should_panic = "message"]` and
+/// `#[should_panic(expected = "message")]`.
+fn test_attribute(attr: TokenStream) -> Option<TestAttribute> {
+    let mut attr = attr.into_iter();
+    let name = match attr.next() {
+        Some(TokenTree::Ident(name)) => name,
+        _ => return None,
+    };
+    let rest = attr.collect::<Vec<_>>();
+    if name == "ignore" {
+        match &rest[..] {
+            [] => {}
+            [TokenTree::Punct(eq), TokenTree::Literal(_)] if eq.as_char() == '=' => {}
+            _ => panic!("malformed `#[ignore]` attribute"),
+        }
+        return Some(TestAttribute::Ignore);
+    }
+    if name != "should_panic" {
+        return None;
+    }
+    let expected = match &rest[..] {
+        [] => None,
+        [TokenTree::Punct(eq), TokenTree::Literal(msg)] if eq.as_char() == '=' => Some(msg.clone()),
+        [TokenTree::Group(args)] if args.delimiter() == Delimiter::Parenthesis => {
+            match &args.stream().into_iter().collect::<Vec<_>>()[..] {
+                [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(msg)]
+                    if key == "expected" && eq.as_char() == '=' =>
+                {
+                    Some(msg.clone())
+                }
+                _ => panic!("malformed `#[should_panic]` attribute"),
+            }
+        }
+        _ => panic!("malformed `#[should_panic]` attribute"),
+    };
+    Some(TestAttribute::ShouldPanic(match expected {
+        Some(msg) => quote!(::wasm_bindgen_test::__rt::ShouldPanic::YesWithMessage(#msg)),
+        None => quote!(::wasm_bindgen_test::__rt::ShouldPanic::Yes),
+    }))
 }
@@ -0,0 +1,72 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use futures::prelude::*;
+use sample::Timeout;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+// How many tests have been set up and not yet torn down, which can be more
+// than one as tests may run concurrently.
+thread_local! {
+    static ACTIVE: Cell<u32> = Cell::new(0);
+    static READY: Cell<u32> = Cell::new(0);
+}
+
+#[wasm_bindgen_test_setup]
+fn setup() {
+    ACTIVE.with(|a| a.set(a.get() + 1));
+}
+
+#[wasm_bindgen_test_setup(async)]
+fn setup_later() -> impl Future<Item = (), Error = JsValue> {
+    Timeout::new(Duration::from_millis(10)).map(|()| {
+        READY.with(|r| r.set(r.get() + 1));
+    })
+}
+
+#[wasm_bindgen_test_teardown]
+fn teardown() {
+    console_log!("tearing down");
+    ACTIVE.with(|a| a.set(a.get() - 1));
+    READY.with(|r| r.set(r.get() - 1));
+}
+
+fn assert_set_up() {
+    assert!(ACTIVE.with(|a| a.get()) > 0);
+    assert!(READY.with(|r| r.get()) > 0);
+}
+
+#[wasm_bindgen_test]
+fn sees_fixture() {
+    assert_set_up();
+}
+
+#[wasm_bindgen_test]
+fn fail_with_fixture() {
+    panic!("teardown still runs after this test");
+}
+
+#[wasm_bindgen_test(async)]
+fn sees_fixture_async() -> impl Future<Item = (), Error = JsValue> {
+    Timeout::new(Duration::from_millis(10)).map(|()| assert_set_up())
+}
+
+// Teardown hooks run even if an earlier one failed, so the output of the
+// failing test below shows both panics.
+pub mod failing_teardown {
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test_teardown]
+    fn teardown() {
+        panic!("this teardown hook fails");
+    }
+
+    #[wasm_bindgen_test_teardown]
+    fn teardown_again() {
+        panic!("this teardown hook fails as well");
+    }
+
+    #[wasm_bindgen_test]
+    fn fail_in_teardown() {}
+}
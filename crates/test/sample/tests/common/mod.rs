@@ -40,3 +40,48 @@ fn fail_after_3s() -> impl Future<Item = (), Error = JsValue> {
         })
     })
 }
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn should_panic() {
+    panic!("this test is expected to panic");
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "expected to panic")]
+fn should_panic_with_message() {
+    panic!("this test is expected to panic");
+}
+
+#[wasm_bindgen_test]
+#[should_panic(expected = "some other message")]
+fn should_panic_with_wrong_message() {
+    panic!("this test is expected to panic");
+}
+
+#[wasm_bindgen_test]
+#[should_panic]
+fn should_panic_but_passes() {
+    console_log!("this test doesn't panic");
+}
+
+#[wasm_bindgen_test]
+#[ignore]
+fn ignored() {
+    panic!("only run with `--ignored` or `--include-ignored`");
+}
+
+#[wasm_bindgen_test(async, timeout_ms = 500)]
+fn fail_timeout() -> impl Future<Item = (), Error = JsValue> {
+    console_log!("this test takes longer than its timeout");
+    Timeout::new(Duration::new(1, 0))
+}
+
+#[wasm_bindgen_bench]
+fn bench_sum(b: &mut Bencher) {
+    let data = (0..1024u32).collect::<Vec<_>>();
+    b.bytes = (data.len() * 4) as u64;
+    b.iter(|| data.iter().sum::<u32>());
+}
+
+pub mod hooks;
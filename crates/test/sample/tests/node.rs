@@ -3,4 +3,6 @@ extern crate sample;
 extern crate wasm_bindgen;
 extern crate wasm_bindgen_test;
 
+wasm_bindgen_test::wasm_bindgen_test_configure!(concurrency = 2);
+
 pub mod common;
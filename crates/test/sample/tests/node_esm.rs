@@ -0,0 +1,21 @@
+extern crate futures;
+extern crate sample;
+extern crate wasm_bindgen;
+extern crate wasm_bindgen_test;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_node_esm);
+
+pub mod common;
+
+#[wasm_bindgen(inline_js = "export function add(a, b) { return a + b; }")]
+extern "C" {
+    fn add(a: u32, b: u32) -> u32;
+}
+
+#[wasm_bindgen_test]
+fn es_module_snippet() {
+    assert_eq!(add(1, 2), 3);
+}
@@ -4,7 +4,11 @@
 
 #![deny(missing_docs)]
 
-pub use wasm_bindgen_test_macro::wasm_bindgen_test;
+pub use wasm_bindgen_test_macro::{
+    wasm_bindgen_bench, wasm_bindgen_test, wasm_bindgen_test_setup, wasm_bindgen_test_teardown,
+};
+
+pub use __rt::bench::Bencher;
 
 /// Helper macro which acts like `println!` only routes to `console.log`
 /// instead.
@@ -29,6 +33,17 @@ macro_rules! console_log {
 ///
 /// * `run_in_browser` - requires that this test is run in a browser rather than
 ///   node.js, which is the default for executing tests.
+/// * `run_in_worker` - requires that this test is run in a dedicated web worker
+///   started by a browser page, where there's no `window` or DOM.
+/// * `run_in_shared_worker` - requires that this test is run in a shared web
+///   worker started by a browser page.
+/// * `run_in_node_esm` - requires that this test is run in node.js with the
+///   bindings generated as ES modules rather than CommonJS, for crates whose JS
+///   snippets are ES modules. This needs a version of node.js supporting
+///   `--experimental-modules`.
+/// * `concurrency = N` - executes up to `N` asynchronous tests at the same time
+///   rather than one after the other, unless overridden with `--test-threads`
+///   at runtime.
 ///
 /// This macro may be invoked at most one time per test suite (an entire binary
 /// like `tests/foo.rs`, not per module)
@@ -40,6 +55,36 @@ macro_rules! wasm_bindgen_test_configure {
         pub static __WBG_TEST_RUN_IN_BROWSER: [u8; 1] = [0x01];
         $crate::wasm_bindgen_test_configure!($($others)*);
     );
+    (run_in_worker $($others:tt)*) => (
+        #[link_section = "__wasm_bindgen_test_unstable"]
+        #[cfg(target_arch = "wasm32")]
+        pub static __WBG_TEST_RUN_IN_WORKER: [u8; 1] = [0x03];
+        $crate::wasm_bindgen_test_configure!($($others)*);
+    );
+    (run_in_shared_worker $($others:tt)*) => (
+        #[link_section = "__wasm_bindgen_test_unstable"]
+        #[cfg(target_arch = "wasm32")]
+        pub static __WBG_TEST_RUN_IN_SHARED_WORKER: [u8; 1] = [0x04];
+        $crate::wasm_bindgen_test_configure!($($others)*);
+    );
+    (run_in_node_esm $($others:tt)*) => (
+        #[link_section = "__wasm_bindgen_test_unstable"]
+        #[cfg(target_arch = "wasm32")]
+        pub static __WBG_TEST_RUN_IN_NODE_ESM: [u8; 1] = [0x05];
+        $crate::wasm_bindgen_test_configure!($($others)*);
+    );
+    (concurrency = $n:literal $($others:tt)*) => (
+        #[link_section = "__wasm_bindgen_test_unstable"]
+        #[cfg(target_arch = "wasm32")]
+        pub static __WBG_TEST_CONCURRENCY: [u8; 5] = [
+            0x02,
+            ($n as u32) as u8,
+            (($n as u32) >> 8) as u8,
+            (($n as u32) >> 16) as u8,
+            (($n as u32) >> 24) as u8,
+        ];
+        $crate::wasm_bindgen_test_configure!($($others)*);
+    );
     () => ()
 }
 
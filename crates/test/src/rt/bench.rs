@@ -0,0 +1,157 @@
+//! Support for `#[wasm_bindgen_bench]` benchmarks.
+//!
+//! This mirrors the `Bencher` of libtest: a benchmark is a function taking a
+//! `&mut Bencher`, which calls `Bencher::iter` with the code to measure. When
+//! the test harness is passed `--bench` that code is run repeatedly for a
+//! warm-up period and then a number of samples, each sample being timed with
+//! `performance.now()`. Otherwise benchmarks are only run once, as a test, to
+//! make sure they work.
+
+use std::cmp;
+use std::mem;
+use std::ptr;
+
+use wasm_bindgen::prelude::*;
+
+// How long the code being benchmarked is run for before it's measured, in
+// milliseconds.
+const WARMUP_MS: f64 = 100.0;
+
+// How long each sample should take, in milliseconds, which determines how many
+// iterations it's made of.
+const SAMPLE_MS: f64 = 10.0;
+
+// How many samples are taken, unless they take longer than `MAX_MS`
+// milliseconds altogether in which case sampling stops early.
+const SAMPLES: usize = 50;
+const MAX_MS: f64 = 3_000.0;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// Manager of the benchmarking runs.
+///
+/// This is fed into functions marked with `#[wasm_bindgen_bench]` to allow
+/// for setup and teardown of the code being measured, which is passed to
+/// `iter`.
+pub struct Bencher {
+    /// The number of bytes processed by each iteration, if set this is used to
+    /// report the throughput of the benchmark.
+    pub bytes: u64,
+    measure: bool,
+    summary: Option<Summary>,
+}
+
+/// Statistics of a benchmark, where all times are in nanoseconds per
+/// iteration.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Summary {
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    /// Throughput in megabytes per second, if `Bencher::bytes` was set.
+    pub mb_s: Option<f64>,
+}
+
+impl Bencher {
+    pub(crate) fn new(measure: bool) -> Bencher {
+        Bencher {
+            bytes: 0,
+            measure,
+            summary: None,
+        }
+    }
+
+    /// Callback for benchmark functions to run in their body.
+    pub fn iter<T, F: FnMut() -> T>(&mut self, mut inner: F) {
+        if !self.measure {
+            black_box(inner());
+            return;
+        }
+
+        // Warm up for a while, which also gives us a rough idea of how long
+        // each iteration takes so we can pick the size of each sample.
+        let start = performance_now();
+        let mut n = 0;
+        let mut elapsed = 0.0;
+        while n == 0 || elapsed < WARMUP_MS {
+            black_box(inner());
+            n += 1;
+            elapsed = performance_now() - start;
+        }
+        let iterations = cmp::max(1, (SAMPLE_MS * n as f64 / elapsed) as u64);
+
+        let start = performance_now();
+        let mut samples = Vec::with_capacity(SAMPLES);
+        while samples.len() < SAMPLES {
+            let sample_start = performance_now();
+            for _ in 0..iterations {
+                black_box(inner());
+            }
+            let now = performance_now();
+            samples.push((now - sample_start) * 1_000_000.0 / iterations as f64);
+            if samples.len() >= 2 && now - start > MAX_MS {
+                break;
+            }
+        }
+
+        self.summary = Some(Summary::new(&mut samples, self.bytes));
+    }
+
+    pub(crate) fn summary(&self) -> Option<Summary> {
+        self.summary
+    }
+}
+
+impl Summary {
+    fn new(samples: &mut [f64], bytes: u64) -> Summary {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / (n - 1.0);
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mid = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2.0
+        } else {
+            samples[mid]
+        };
+        let mb_s = if bytes > 0 && mean > 0.0 {
+            Some(bytes as f64 * 1_000.0 / mean)
+        } else {
+            None
+        };
+        Summary {
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            mb_s,
+        }
+    }
+}
+
+/// Formats a number of nanoseconds like libtest, rounded and with thousands
+/// separators.
+pub(crate) fn fmt_thousands_sep(n: f64) -> String {
+    let digits = format!("{}", n.round().max(0.0) as u64);
+    let mut dst = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            dst.push(',');
+        }
+        dst.push(c);
+    }
+    dst
+}
+
+/// Prevents the optimizer from removing the computation of `dummy`, like
+/// libtest's `black_box`.
+fn black_box<T>(dummy: T) -> T {
+    unsafe {
+        let ret = ptr::read_volatile(&dummy);
+        mem::forget(dummy);
+        ret
+    }
+}
@@ -54,28 +54,38 @@ impl super::Formatter for Browser {
         self.writeln(&format!("test {} ... {}", name, s));
     }
 
+    fn log_ignored(&self, name: &str) {
+        self.writeln(&format!("test {} ... ignored", name));
+    }
+
     fn stringify_error(&self, err: &JsValue) -> String {
-        // TODO: this should be a checked cast to `Error`
-        let err = Error::from(err.clone());
-        let name = String::from(err.name());
-        let message = String::from(err.message());
-        let err = BrowserError::from(JsValue::from(err));
-        let stack = err.stack();
+        stringify_error(err)
+    }
+}
 
-        let header = format!("{}: {}", name, message);
-        let stack = match stack.as_string() {
-            Some(stack) => stack,
-            None => return header,
-        };
+/// Converts a thrown value into a string including its stack trace, in a way
+/// which works across browsers.
+pub(crate) fn stringify_error(err: &JsValue) -> String {
+    // TODO: this should be a checked cast to `Error`
+    let err = Error::from(err.clone());
+    let name = String::from(err.name());
+    let message = String::from(err.message());
+    let err = BrowserError::from(JsValue::from(err));
+    let stack = err.stack();
 
-        // If the `stack` variable contains the name/message already, this is
-        // probably a chome-like error which is already rendered well, so just
-        // return this info
-        if stack.contains(&header) {
-            return stack;
-        }
+    let header = format!("{}: {}", name, message);
+    let stack = match stack.as_string() {
+        Some(stack) => stack,
+        None => return header,
+    };
 
-        // Fallback to make sure we don't lose any info
-        format!("{}\n{}", header, stack)
+    // If the `stack` variable contains the name/message already, this is
+    // probably a chome-like error which is already rendered well, so just
+    // return this info
+    if stack.contains(&header) {
+        return stack;
     }
+
+    // Fallback to make sure we don't lose any info
+    format!("{}\n{}", header, stack)
 }
@@ -1,4 +1,4 @@
-//! Runtime detection of whether we're in node.js or a browser.
+//! Runtime detection of whether we're in node.js, a browser or a web worker.
 
 use js_sys;
 use wasm_bindgen::prelude::*;
@@ -9,6 +9,8 @@ extern "C" {
     type This;
     #[wasm_bindgen(method, getter, structural, js_name = self)]
     fn self_(me: &This) -> JsValue;
+    #[wasm_bindgen(method, getter, structural, js_name = WorkerGlobalScope)]
+    fn worker_global_scope(me: &This) -> JsValue;
 }
 
 /// Returns whether it's likely we're executing in a browser environment, as
@@ -24,3 +26,13 @@ pub fn is_browser() -> bool {
     // browsers.
     js_sys::global().unchecked_into::<This>().self_() != JsValue::undefined()
 }
+
+/// Returns whether we're executing in a web worker, either a dedicated or a
+/// shared one, as opposed to the main thread of a browser or node.js.
+pub fn is_worker() -> bool {
+    // The `WorkerGlobalScope` interface is only exposed in workers.
+    js_sys::global()
+        .unchecked_into::<This>()
+        .worker_global_scope()
+        != JsValue::undefined()
+}
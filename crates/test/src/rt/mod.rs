@@ -88,27 +88,41 @@
 // always of course welcome!
 
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt;
+use std::panic::{self, PanicInfo};
 use std::rc::Rc;
+use std::sync::Once;
 
 use console_error_panic_hook;
 use futures::future;
 use futures::prelude::*;
-use js_sys::{Array, Function, Promise};
+use futures::stream;
+use js_sys::{Array, Date, Function, Promise, Reflect, JSON};
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::future_to_promise;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
 
-// Maximum number of tests to execute concurrently. Eventually this should be a
-// configuration option specified at runtime or at compile time rather than
-// baked in here.
+use self::bench::{Bencher, Summary};
+use self::report::{Format, Report};
+
+// Default maximum number of tests to execute concurrently, which can be
+// changed with `wasm_bindgen_test_configure!(concurrency = N)` or the
+// `--test-threads` flag.
 //
 // Currently the default is 1 because the DOM has a lot of shared state, and
 // conccurrently doing things by default would likely end up in a bad situation.
-const CONCURRENCY: usize = 1;
+const DEFAULT_CONCURRENCY: usize = 1;
+
+// Number of milliseconds after which an asynchronous test fails if it hasn't
+// finished yet, unless it specifies its own `timeout_ms`.
+const DEFAULT_TIMEOUT_MS: u32 = 10_000;
 
+pub mod bench;
 pub mod browser;
 pub mod detect;
 pub mod node;
+mod report;
+pub mod worker;
 
 /// Runtime test harness support instantiated in JS.
 ///
@@ -120,11 +134,58 @@ pub struct Context {
 }
 
 struct State {
-    /// An optional filter used to restrict which tests are actually executed
-    /// and which are ignored. This is passed via the `args` function which
-    /// comes from the command line of `wasm-bindgen-test-runner`. Currently
-    /// this is the only "CLI option"
-    filter: RefCell<Option<String>>,
+    /// Filters used to restrict which tests are actually executed and which
+    /// are ignored. These are passed via the `args` function which comes from
+    /// the command line of `wasm-bindgen-test-runner`. A test is executed if
+    /// it matches any of the filters, or if there are none.
+    filters: RefCell<Vec<String>>,
+
+    /// Tests matching any of these filters are ignored, configured with
+    /// `--skip`.
+    skip: RefCell<Vec<String>>,
+
+    /// Whether filters have to match the full name of a test rather than a
+    /// part of it, configured with `--exact`.
+    exact: Cell<bool>,
+
+    /// Whether console output of tests is left alone rather than captured,
+    /// configured with `--nocapture`.
+    nocapture: Cell<bool>,
+
+    /// Whether tests are only listed rather than executed, configured with
+    /// `--list`.
+    list: Cell<bool>,
+
+    /// Whether test results are printed as a single character each rather
+    /// than one line each, configured with `--format terse` or `--quiet`.
+    terse: Cell<bool>,
+
+    /// The line of `--format terse` output which hasn't been printed yet.
+    terse_line: RefCell<String>,
+
+    /// Maximum number of tests to execute concurrently, configured with
+    /// `--test-threads`.
+    concurrency: Cell<usize>,
+
+    /// The structured report of the test run, if one was requested with
+    /// `--format json` or `--format junit`.
+    report: RefCell<Option<report::Report>>,
+
+    /// When the test run started, as returned by `Date.now()`.
+    start: Cell<f64>,
+
+    /// Whether benchmarks are measured rather than run once as tests, in
+    /// which case tests are ignored, configured with `--bench`.
+    bench: Cell<bool>,
+
+    /// The mean time per iteration of benchmarks from a previous run, in
+    /// nanoseconds, to compare against. This is read by
+    /// `wasm-bindgen-test-runner` from the file given with `--baseline`.
+    baseline: RefCell<HashMap<String, f64>>,
+
+    /// Whether `#[ignore]` tests are run, configured with the
+    /// `--include-ignored` and `--ignored` flags.
+    run_ignored: Cell<RunIgnored>,
 
     /// Counter of the number of tests that have succeeded.
     succeeded: Cell<usize>,
@@ -132,6 +193,13 @@ struct State {
     /// Counter of the number of tests that have been ignored
     ignored: Cell<usize>,
 
+    /// Names of the tests ignored due to `#[ignore]` or `--bench`, which are
+    /// logged once all tests have been collected.
+    ignored_tests: RefCell<Vec<String>>,
+
+    /// Counter of the number of benchmarks that have been measured.
+    measured: Cell<usize>,
+
     /// A list of all tests which have failed.
     ///
     /// Each test listed here is paired with a `JsValue` that represents the
@@ -146,6 +214,10 @@ struct State {
     /// of asynchronous work, so they're sitting on the running list.
     running: RefCell<Vec<Test>>,
 
+    /// Setup and teardown hooks of each module, by module path, registered
+    /// with `register_hook_sync` and `register_hook_async`.
+    hooks: Rc<RefCell<HashMap<String, ModuleHooks>>>,
+
     /// How to actually format output, either node.js or browser-specific
     /// implementation.
     formatter: Box<dyn Formatter>,
@@ -159,6 +231,53 @@ struct Test {
     name: String,
     future: Box<dyn Future<Item = (), Error = JsValue>>,
     output: Rc<RefCell<Output>>,
+    should_panic: ShouldPanic,
+
+    /// Whether this is a `#[wasm_bindgen_bench]` benchmark.
+    bench: bool,
+
+    /// When the test started executing, as returned by `Date.now()`.
+    start: f64,
+}
+
+/// Whether a test is expected to panic, as configured with `#[should_panic]`.
+///
+/// This mirrors the type of the same name in libtest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShouldPanic {
+    /// The test is expected to not panic.
+    No,
+    /// The test is expected to panic with any message.
+    Yes,
+    /// The test is expected to panic with a message containing this string.
+    YesWithMessage(&'static str),
+}
+
+/// Whether a hook runs before or after each test of its module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HookKind {
+    /// The hook runs before each test, as `#[wasm_bindgen_test_setup]`.
+    Setup,
+    /// The hook runs after each test, even if the test or its setup failed, as
+    /// `#[wasm_bindgen_test_teardown]`.
+    Teardown,
+}
+
+/// A setup or teardown hook, which creates a new future each time it runs.
+type Hook = Rc<dyn Fn() -> Box<dyn Future<Item = (), Error = JsValue>>>;
+
+/// The hooks registered for the tests of a module.
+#[derive(Clone, Default)]
+struct ModuleHooks {
+    setup: Vec<Hook>,
+    teardown: Vec<Hook>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum RunIgnored {
+    Yes,
+    No,
+    Only,
 }
 
 /// Captured output of each test.
@@ -169,6 +288,16 @@ struct Output {
     info: String,
     warn: String,
     error: String,
+
+    /// The message of the panic which happened while running the test, if
+    /// any, recorded by our panic hook.
+    panic: Option<String>,
+
+    /// Whether console output isn't captured, due to `--nocapture`.
+    nocapture: bool,
+
+    /// The statistics of a benchmark, once it has been measured.
+    bench: Option<Summary>,
 }
 
 trait Formatter {
@@ -178,6 +307,9 @@ trait Formatter {
     /// Log the result of a test, either passing or failing.
     fn log_test(&self, name: &str, result: &Result<(), JsValue>);
 
+    /// Log that a test was skipped because it's marked with `#[ignore]`.
+    fn log_ignored(&self, name: &str);
+
     /// Convert a thrown value into a string, using platform-specific apis
     /// perhaps to turn the error into a string.
     fn stringify_error(&self, val: &JsValue) -> String;
@@ -192,6 +324,11 @@ extern "C" {
     // General-purpose conversion into a `String`.
     #[wasm_bindgen(js_name = String)]
     fn stringify(val: &JsValue) -> String;
+
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(f: &Function, ms: u32) -> JsValue;
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(id: &JsValue);
 }
 
 /// Internal implementation detail of the `console_log!` macro.
@@ -208,20 +345,37 @@ impl Context {
     /// tests.
     #[wasm_bindgen(constructor)]
     pub fn new() -> Context {
-        console_error_panic_hook::set_once();
+        static SET_HOOK: Once = Once::new();
+        SET_HOOK.call_once(|| panic::set_hook(Box::new(panic_hook)));
 
-        let formatter = match node::Node::new() {
-            Some(node) => Box::new(node) as Box<dyn Formatter>,
-            None => Box::new(browser::Browser::new()),
+        let formatter = match (node::Node::new(), worker::Worker::new()) {
+            (Some(node), _) => Box::new(node) as Box<dyn Formatter>,
+            (None, Some(worker)) => Box::new(worker),
+            (None, None) => Box::new(browser::Browser::new()),
         };
         Context {
             state: Rc::new(State {
-                filter: Default::default(),
+                filters: Default::default(),
+                skip: Default::default(),
+                exact: Default::default(),
+                nocapture: Default::default(),
+                list: Default::default(),
+                terse: Default::default(),
+                terse_line: Default::default(),
+                concurrency: Cell::new(DEFAULT_CONCURRENCY),
+                report: Default::default(),
+                start: Default::default(),
+                bench: Default::default(),
+                baseline: Default::default(),
+                run_ignored: Cell::new(RunIgnored::No),
                 failures: Default::default(),
                 ignored: Default::default(),
+                ignored_tests: Default::default(),
+                measured: Default::default(),
                 remaining: Default::default(),
                 running: Default::default(),
                 succeeded: Default::default(),
+                hooks: Default::default(),
                 formatter,
             }),
         }
@@ -230,23 +384,54 @@ impl Context {
     /// Inform this context about runtime arguments passed to the test
     /// harness.
     ///
-    /// Eventually this will be used to support flags, but for now it's just
-    /// used to support test filters.
+    /// This supports test filters along with the libtest flags which make
+    /// sense for wasm: `--include-ignored`, `--ignored`, `--exact`,
+    /// `--nocapture`, `--list`, `--format`, `--quiet`, `--skip`,
+    /// `--test-threads` and `--bench`. Here `--format` also accepts `json` and
+    /// `junit` to collect a report retrieved with `report` afterwards, and
+    /// `--baseline-data` takes the contents of such a JSON report from a
+    /// previous run to compare benchmarks against.
     pub fn args(&mut self, args: Vec<JsValue>) {
-        // Here we want to reject all flags like `--foo` or `-f` as we don't
-        // support anything, and also we only support at most one non-flag
-        // argument as a test filter.
-        //
-        // Everything else is rejected.
-        let mut filter = self.state.filter.borrow_mut();
-        for arg in args {
-            let arg = arg.as_string().unwrap();
-            if arg.starts_with("-") {
-                panic!("flag {} not supported", arg);
-            } else if filter.is_some() {
-                panic!("more than one filter argument cannot be passed");
+        let mut args = args.into_iter().map(|arg| arg.as_string().unwrap());
+        while let Some(arg) = args.next() {
+            // Like with libtest the value of a flag can either be the next
+            // argument or follow an `=`, as in `--skip foo` or `--skip=foo`.
+            let (flag, value) = match arg.find('=') {
+                Some(i) if arg.starts_with("--") => (&arg[..i], Some(arg[i + 1..].to_string())),
+                _ => (&arg[..], None),
+            };
+            match flag {
+                "--include-ignored" => self.state.run_ignored.set(RunIgnored::Yes),
+                "--ignored" => self.state.run_ignored.set(RunIgnored::Only),
+                "--exact" => self.state.exact.set(true),
+                "--nocapture" => self.state.nocapture.set(true),
+                "--list" => self.state.list.set(true),
+                "--bench" => self.state.bench.set(true),
+                "--baseline-data" => {
+                    let value = flag_value(flag, value, &mut args);
+                    *self.state.baseline.borrow_mut() = parse_baseline(&value);
+                }
+                "-q" | "--quiet" => self.state.terse.set(true),
+                "--format" => match &flag_value(flag, value, &mut args)[..] {
+                    "pretty" => self.state.terse.set(false),
+                    "terse" => self.state.terse.set(true),
+                    "json" => *self.state.report.borrow_mut() = Some(Report::new(Format::Json)),
+                    "junit" => *self.state.report.borrow_mut() = Some(Report::new(Format::Junit)),
+                    other => panic!("unsupported value `{}` for `--format`", other),
+                },
+                "--skip" => {
+                    let value = flag_value(flag, value, &mut args);
+                    self.state.skip.borrow_mut().push(value);
+                }
+                // There's only a single thread in wasm, so this is the number
+                // of asynchronous tests which are executed concurrently.
+                "--test-threads" => match flag_value(flag, value, &mut args).parse::<usize>() {
+                    Ok(n) if n > 0 => self.state.concurrency.set(n),
+                    _ => panic!("argument for --test-threads must be a number > 0"),
+                },
+                _ if arg.starts_with("-") => panic!("flag {} not supported", arg),
+                _ => self.state.filters.borrow_mut().push(arg.clone()),
             }
-            *filter = Some(arg);
         }
     }
 
@@ -260,16 +445,12 @@ impl Context {
     /// The promise returned resolves to either `true` if all tests passed or
     /// `false` if at least one test failed.
     pub fn run(&self, tests: Vec<JsValue>) -> Promise {
-        let noun = if tests.len() == 1 { "test" } else { "tests" };
-        self.state
-            .formatter
-            .writeln(&format!("running {} {}", tests.len(), noun));
-        self.state.formatter.writeln("");
-
         // Execute all our test functions through their wasm shims (unclear how
         // to pass native function pointers around here). Each test will
         // execute one of the `execute_*` tests below which will push a
-        // future onto our `remaining` list, which we'll process later.
+        // future onto our `remaining` list, which we'll process later. Setup
+        // and teardown hooks are passed in the same way, and register
+        // themselves with `register_hook_*` instead.
         let cx_arg = (self as *const Context as u32).into();
         for test in tests {
             match Function::from(test).call1(&JsValue::null(), &cx_arg) {
@@ -283,6 +464,53 @@ impl Context {
             }
         }
 
+        // With `--list` all that's left to do is print the names of the tests
+        // which would have been executed.
+        if self.state.list.get() {
+            let remaining = self.state.remaining.borrow();
+            for test in remaining.iter() {
+                let kind = if test.bench { "bench" } else { "test" };
+                self.state
+                    .formatter
+                    .writeln(&format!("{}: {}", test.name, kind));
+            }
+            let benches = remaining.iter().filter(|t| t.bench).count();
+            let tests = remaining.len() - benches;
+            let noun = if tests == 1 { "test" } else { "tests" };
+            let bench_noun = if benches == 1 {
+                "benchmark"
+            } else {
+                "benchmarks"
+            };
+            self.state.formatter.writeln("");
+            self.state
+                .formatter
+                .writeln(&format!("{} {}, {} {}", tests, noun, benches, bench_noun));
+            return Promise::resolve(&JsValue::TRUE);
+        }
+
+        // Now that we know which tests are going to run, and which are
+        // ignored, let the user know.
+        let ignored = self.state.ignored_tests.replace(Vec::new());
+        let count = self.state.remaining.borrow().len() + ignored.len();
+        let noun = if count == 1 { "test" } else { "tests" };
+        self.state
+            .formatter
+            .writeln(&format!("running {} {}", count, noun));
+        self.state.formatter.writeln("");
+        for name in ignored.iter() {
+            if self.state.terse.get() {
+                self.state.log_terse('i');
+            } else {
+                self.state.formatter.log_ignored(name);
+            }
+            if let Some(report) = &mut *self.state.report.borrow_mut() {
+                report.ignored(name);
+            }
+        }
+
+        self.state.start.set(Date::now());
+
         // Now that we've collected all our tests we wrap everything up in a
         // future to actually do all the processing, and pass it out to JS as a
         // `Promise`.
@@ -293,6 +521,19 @@ impl Context {
     }
 }
 
+#[wasm_bindgen(js_class = WasmBindgenTestContext)]
+impl Context {
+    /// Returns the structured report of the test run once `run` has
+    /// finished, if `--format json` or `--format junit` was passed.
+    pub fn report(&self) -> Option<String> {
+        let report = self.state.report.borrow();
+        let report = report.as_ref()?;
+        let filtered_out = self.state.ignored.get() - report.ignored_count();
+        let exec_time = (Date::now() - self.state.start.get()) / 1000.0;
+        Some(report.render(filtered_out, exec_time))
+    }
+}
+
 scoped_tls::scoped_thread_local!(static CURRENT_OUTPUT: RefCell<Output>);
 
 /// Handler for `console.log` invocations.
@@ -336,6 +577,23 @@ pub fn __wbgtest_console_error(args: &Array) {
     record(args, |output| &mut output.error)
 }
 
+/// Our panic hook, which records the panic message for `#[should_panic]` tests
+/// before printing it with `console_error_panic_hook`.
+fn panic_hook(info: &PanicInfo) {
+    if CURRENT_OUTPUT.is_set() {
+        let payload = info.payload();
+        let msg = match payload.downcast_ref::<&str>() {
+            Some(s) => s.to_string(),
+            None => match payload.downcast_ref::<String>() {
+                Some(s) => s.clone(),
+                None => "Box<Any>".to_string(),
+            },
+        };
+        CURRENT_OUTPUT.with(|output| output.borrow_mut().panic = Some(msg));
+    }
+    console_error_panic_hook::hook(info);
+}
+
 fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) {
     if !CURRENT_OUTPUT.is_set() {
         return;
@@ -343,6 +601,9 @@ fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) {
 
     CURRENT_OUTPUT.with(|output| {
         let mut out = output.borrow_mut();
+        if out.nocapture {
+            return;
+        }
         let dst = dst(&mut out);
         args.for_each(&mut |val, idx, _array| {
             if idx != 0 {
@@ -357,43 +618,166 @@ fn record(args: &Array, dst: impl FnOnce(&mut Output) -> &mut String) {
 impl Context {
     /// Entry point for a synchronous test in wasm. The `#[wasm_bindgen_test]`
     /// macro generates invocations of this method.
-    pub fn execute_sync(&self, name: &str, f: impl FnOnce() + 'static) {
-        self.execute(name, future::lazy(|| Ok(f())));
+    pub fn execute_sync(
+        &self,
+        name: &str,
+        f: impl FnOnce() + 'static,
+        should_panic: ShouldPanic,
+        ignore: bool,
+    ) {
+        self.execute(name, future::lazy(|| Ok(f())), should_panic, ignore, false);
     }
 
     /// Entry point for an asynchronous in wasm. The
     /// `#[wasm_bindgen_test(async)]` macro generates invocations of this
     /// method.
-    pub fn execute_async<F>(&self, name: &str, f: impl FnOnce() -> F + 'static)
+    ///
+    /// The test fails if it doesn't finish within `timeout_ms` milliseconds,
+    /// or `DEFAULT_TIMEOUT_MS` if that's `None`.
+    pub fn execute_async<F>(
+        &self,
+        name: &str,
+        f: impl FnOnce() -> F + 'static,
+        should_panic: ShouldPanic,
+        ignore: bool,
+        timeout_ms: Option<u32>,
+    ) where
+        F: Future<Item = (), Error = JsValue> + 'static,
+    {
+        let test = Timeout {
+            test: future::lazy(f),
+            ms: timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS),
+            timer: None,
+        };
+        self.execute(name, test, should_panic, ignore, false)
+    }
+
+    /// Entry point for a benchmark in wasm. The `#[wasm_bindgen_bench]` macro
+    /// generates invocations of this method.
+    ///
+    /// Benchmarks are only measured with `--bench`, otherwise they're run once
+    /// like a test.
+    pub fn execute_bench(&self, name: &str, f: impl FnOnce(&mut Bencher) + 'static, ignore: bool) {
+        let measure = self.state.bench.get();
+        let bench = future::lazy(move || {
+            let mut bencher = Bencher::new(measure);
+            f(&mut bencher);
+            CURRENT_OUTPUT.with(|output| output.borrow_mut().bench = bencher.summary());
+            Ok(())
+        });
+        self.execute(name, bench, ShouldPanic::No, ignore, true)
+    }
+
+    /// Registers a synchronous setup or teardown hook for the tests of
+    /// `module`. The `#[wasm_bindgen_test_setup]` and
+    /// `#[wasm_bindgen_test_teardown]` macros generate invocations of this
+    /// method.
+    pub fn register_hook_sync(&self, module: &str, kind: HookKind, f: impl Fn() + 'static) {
+        let f = Rc::new(f);
+        self.register_hook(
+            module,
+            kind,
+            Rc::new(move || {
+                let f = f.clone();
+                Box::new(future::lazy(move || Ok(f())))
+            }),
+        );
+    }
+
+    /// Registers an asynchronous setup or teardown hook for the tests of
+    /// `module`. The `#[wasm_bindgen_test_setup(async)]` and
+    /// `#[wasm_bindgen_test_teardown(async)]` macros generate invocations of
+    /// this method.
+    ///
+    /// Like a test, the hook fails if it doesn't finish within
+    /// `DEFAULT_TIMEOUT_MS` milliseconds.
+    pub fn register_hook_async<F>(&self, module: &str, kind: HookKind, f: impl Fn() -> F + 'static)
     where
         F: Future<Item = (), Error = JsValue> + 'static,
     {
-        self.execute(name, future::lazy(f))
+        let f = Rc::new(f);
+        self.register_hook(
+            module,
+            kind,
+            Rc::new(move || {
+                let f = f.clone();
+                Box::new(Timeout {
+                    test: future::lazy(move || f()),
+                    ms: DEFAULT_TIMEOUT_MS,
+                    timer: None,
+                })
+            }),
+        );
+    }
+
+    fn register_hook(&self, module: &str, kind: HookKind, hook: Hook) {
+        let mut hooks = self.state.hooks.borrow_mut();
+        let hooks = hooks
+            .entry(module.to_string())
+            .or_insert_with(ModuleHooks::default);
+        match kind {
+            HookKind::Setup => hooks.setup.push(hook),
+            HookKind::Teardown => hooks.teardown.push(hook),
+        }
     }
 
-    fn execute(&self, name: &str, test: impl Future<Item = (), Error = JsValue> + 'static) {
+    fn execute(
+        &self,
+        name: &str,
+        test: impl Future<Item = (), Error = JsValue> + 'static,
+        should_panic: ShouldPanic,
+        ignore: bool,
+        bench: bool,
+    ) {
         // If our test is filtered out, record that it was filtered and move
         // on, nothing to do here.
-        let filter = self.state.filter.borrow();
-        if let Some(filter) = &*filter {
-            if !name.contains(filter) {
-                let ignored = self.state.ignored.get();
-                self.state.ignored.set(ignored + 1);
-                return;
+        let matches = |filter: &String| {
+            if self.state.exact.get() {
+                name == filter
+            } else {
+                name.contains(&filter[..])
             }
+        };
+        let filters = self.state.filters.borrow();
+        if (!filters.is_empty() && !filters.iter().any(&matches))
+            || self.state.skip.borrow().iter().any(&matches)
+        {
+            let ignored = self.state.ignored.get();
+            self.state.ignored.set(ignored + 1);
+            return;
+        }
+
+        // Tests marked `#[ignore]` are only run if asked to, and with
+        // `--ignored` nothing but them is run. Like with libtest, `--bench`
+        // also ignores everything but benchmarks. Ignored tests are still
+        // listed with `--list` though.
+        let run_ignored = self.state.run_ignored.get();
+        if run_ignored == RunIgnored::Only && !ignore {
+            self.state.ignored.set(self.state.ignored.get() + 1);
+            return;
+        }
+        let ignored =
+            (run_ignored == RunIgnored::No && ignore) || (self.state.bench.get() && !bench);
+        if ignored && !self.state.list.get() {
+            self.state.ignored_tests.borrow_mut().push(name.to_string());
+            self.state.ignored.set(self.state.ignored.get() + 1);
+            return;
         }
 
         // Looks like we've got a test that needs to be executed! Push it onto
         // the list of remaining tests.
-        let output = Rc::new(RefCell::new(Output::default()));
-        let future = TestFuture {
-            output: output.clone(),
-            test,
-        };
+        let output = Rc::new(RefCell::new(Output {
+            nocapture: self.state.nocapture.get(),
+            ..Output::default()
+        }));
+        let future = with_hooks(name, test, &self.state.hooks, &output);
         self.state.remaining.borrow_mut().push(Test {
             name: name.to_string(),
             future: Box::new(future),
             output,
+            should_panic,
+            bench,
+            start: 0.0,
         });
     }
 }
@@ -426,11 +810,12 @@ impl Future for ExecuteTests {
         // we `poll` it once to ensure we'll receive notifications. We only
         // want to schedule up to a maximum amount of work though, so this may
         // not schedule all tests.
-        while running.len() < CONCURRENCY {
+        while running.len() < self.0.concurrency.get() {
             let mut test = match remaining.pop() {
                 Some(test) => test,
                 None => break,
             };
+            test.start = Date::now();
             let result = match test.future.poll() {
                 Ok(Async::Ready(())) => Ok(()),
                 Ok(Async::NotReady) => {
@@ -460,18 +845,92 @@ impl Future for ExecuteTests {
 
 impl State {
     fn log_test_result(&self, test: Test, result: Result<(), JsValue>) {
-        // Print out information about the test passing or failing
-        self.formatter.log_test(&test.name, &result);
+        let result = check_should_panic(&test, result);
+        let summary = match result {
+            Ok(()) => test.output.borrow().bench,
+            Err(_) => None,
+        };
+
+        // Print out information about the test passing or failing, or the
+        // statistics of a benchmark which was measured. The latter are printed
+        // in full even with `--format terse`, like libtest does.
+        if let Some(summary) = &summary {
+            self.formatter
+                .writeln(&self.bench_line(&test.name, summary));
+        } else if self.terse.get() {
+            self.log_terse(if result.is_ok() { '.' } else { 'F' });
+        } else {
+            self.formatter.log_test(&test.name, &result);
+        }
+
+        if let Some(report) = &mut *self.report.borrow_mut() {
+            let error = result
+                .as_ref()
+                .err()
+                .map(|e| self.formatter.stringify_error(e));
+            let exec_time = (Date::now() - test.start) / 1000.0;
+            let output = test.output.borrow();
+            match &summary {
+                Some(summary) => report.bench(&test.name, exec_time, summary),
+                None => report.test(
+                    &test.name,
+                    error,
+                    exec_time,
+                    format!("{}{}{}", output.debug, output.log, output.info),
+                    format!("{}{}", output.warn, output.error),
+                ),
+            }
+        }
 
         // Save off the test for later processing when we print the final
         // results.
         match result {
+            Ok(()) if summary.is_some() => self.measured.set(self.measured.get() + 1),
             Ok(()) => self.succeeded.set(self.succeeded.get() + 1),
             Err(e) => self.failures.borrow_mut().push((test, e)),
         }
     }
 
+    /// Formats the statistics of a benchmark like libtest, along with how
+    /// they compare to the baseline passed with `--baseline-data`, if any.
+    fn bench_line(&self, name: &str, summary: &Summary) -> String {
+        let mut line = format!(
+            "test {} ... bench: {:>11} ns/iter (+/- {})",
+            name,
+            bench::fmt_thousands_sep(summary.mean),
+            bench::fmt_thousands_sep(summary.std_dev),
+        );
+        if let Some(mb_s) = summary.mb_s {
+            line.push_str(&format!(" = {} MB/s", mb_s as u64));
+        }
+        if let Some(&baseline) = self.baseline.borrow().get(name) {
+            if baseline > 0.0 {
+                line.push_str(&format!(
+                    " (baseline {} ns/iter, {:+.2}%)",
+                    bench::fmt_thousands_sep(baseline),
+                    (summary.mean - baseline) / baseline * 100.0,
+                ));
+            }
+        }
+        line
+    }
+
+    /// Prints the character representing the result of a test with
+    /// `--format terse`, breaking lines at the same width as libtest.
+    fn log_terse(&self, c: char) {
+        let mut line = self.terse_line.borrow_mut();
+        line.push(c);
+        if line.len() == 88 {
+            self.formatter.writeln(&line);
+            line.truncate(0);
+        }
+    }
+
     fn print_results(&self) {
+        let line = self.terse_line.replace(String::new());
+        if !line.is_empty() {
+            self.formatter.writeln(&line);
+        }
         let failures = self.failures.borrow();
         if failures.len() > 0 {
             self.formatter.writeln("\nfailures:\n");
@@ -488,11 +947,13 @@ impl State {
             "test result: {}. \
              {} passed; \
              {} failed; \
-             {} ignored\n",
+             {} ignored; \
+             {} measured\n",
             if failures.len() == 0 { "ok" } else { "FAILED" },
             self.succeeded.get(),
             failures.len(),
             self.ignored.get(),
+            self.measured.get(),
         ));
     }
 
@@ -523,6 +984,127 @@ impl State {
     }
 }
 
+/// Returns the value of `flag`, which is either `value` if it was passed as
+/// `--flag=value` or otherwise the next argument.
+fn flag_value(flag: &str, value: Option<String>, args: &mut dyn Iterator<Item = String>) -> String {
+    value
+        .or_else(|| args.next())
+        .unwrap_or_else(|| panic!("flag {} requires a value", flag))
+}
+
+/// Parses a JSON report of a previous run, as written with `--format json`,
+/// into the mean time per iteration of each benchmark in it. Reports written by
+/// libtest only have the median time, which is used instead.
+fn parse_baseline(report: &str) -> HashMap<String, f64> {
+    let mut baseline = HashMap::new();
+    for line in report.lines() {
+        let event = match JSON::parse(line) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+        let get = |key: &str| Reflect::get(&event, &JsValue::from_str(key)).ok();
+        if get("type").and_then(|t| t.as_string()) != Some("bench".to_string()) {
+            continue;
+        }
+        let name = get("name").and_then(|n| n.as_string());
+        let time = get("mean")
+            .and_then(|t| t.as_f64())
+            .or_else(|| get("median").and_then(|t| t.as_f64()));
+        if let (Some(name), Some(time)) = (name, time) {
+            baseline.insert(name, time);
+        }
+    }
+    baseline
+}
+
+/// Interprets the result of a test according to its `#[should_panic]`
+/// attribute, following libtest: a test which should panic fails unless it
+/// panicked, with a panic message containing the expected one if given.
+fn check_should_panic(test: &Test, result: Result<(), JsValue>) -> Result<(), JsValue> {
+    let expected = match test.should_panic {
+        ShouldPanic::No => return result,
+        ShouldPanic::Yes => None,
+        ShouldPanic::YesWithMessage(msg) => Some(msg),
+    };
+    let output = test.output.borrow();
+    let msg = match (&output.panic, result) {
+        (None, Ok(())) => "test did not panic as expected".to_string(),
+        // A JS exception which isn't a panic still fails the test.
+        (None, Err(e)) => return Err(e),
+        (Some(panic), _) => match expected {
+            Some(expected) if !panic.contains(expected) => format!(
+                "panic did not contain expected string\n      \
+                 panic message: `{:?}`,\n \
+                 expected substring: `{:?}`",
+                panic, expected
+            ),
+            _ => return Ok(()),
+        },
+    };
+    Err(js_sys::Error::new(&msg).into())
+}
+
+/// Wraps a test in the setup and teardown hooks of its module, where the
+/// teardown hooks run even if the setup or the test itself failed.
+///
+/// The test and each hook are wrapped in a `TestFuture` of their own, so a
+/// panic in one of them doesn't keep the others from running. As hooks may be
+/// registered after a test is collected they're only looked up once the test
+/// starts executing.
+fn with_hooks(
+    name: &str,
+    test: impl Future<Item = (), Error = JsValue> + 'static,
+    hooks: &Rc<RefCell<HashMap<String, ModuleHooks>>>,
+    output: &Rc<RefCell<Output>>,
+) -> impl Future<Item = (), Error = JsValue> {
+    let module = match name.rfind("::") {
+        Some(i) => name[..i].to_string(),
+        None => String::new(),
+    };
+    let hooks = hooks.clone();
+    let output = output.clone();
+    future::lazy(move || {
+        let ModuleHooks { setup, teardown } =
+            hooks.borrow().get(&module).cloned().unwrap_or_default();
+        let teardown_output = output.clone();
+        run_setup_hooks(setup, output.clone())
+            .and_then(move |()| TestFuture { output, test })
+            .then(move |result| {
+                run_teardown_hooks(teardown, teardown_output)
+                    .then(move |teardown| result.and(teardown))
+            })
+    })
+}
+
+/// Runs setup hooks one after the other, stopping at the first one which
+/// fails.
+fn run_setup_hooks(
+    hooks: Vec<Hook>,
+    output: Rc<RefCell<Output>>,
+) -> impl Future<Item = (), Error = JsValue> {
+    stream::iter_ok(hooks).for_each(move |hook| TestFuture {
+        output: output.clone(),
+        test: hook(),
+    })
+}
+
+/// Runs teardown hooks one after the other, all of them even if some fail, and
+/// then fails with the error of the first one which failed.
+fn run_teardown_hooks(
+    hooks: Vec<Hook>,
+    output: Rc<RefCell<Output>>,
+) -> impl Future<Item = (), Error = JsValue> {
+    stream::iter_ok(hooks)
+        .fold(Ok(()), move |first: Result<(), JsValue>, hook| {
+            TestFuture {
+                output: output.clone(),
+                test: hook(),
+            }
+            .then(move |result| Ok::<_, JsValue>(first.and(result)))
+        })
+        .and_then(|result| result)
+}
+
 /// A wrapper future around each test
 ///
 /// This future is what's actually executed for each test and is what's stored
@@ -530,7 +1112,9 @@ impl State {
 ///
 /// * First, every time when polled, it configures the `CURRENT_OUTPUT` tls
 ///   variable to capture output for the current test. That way at least when
-///   we've got Rust code running we'll be able to capture output.
+///   we've got Rust code running we'll be able to capture output. As this is
+///   done for each `poll`, output is attributed to the right test even when
+///   several tests are executing concurrently.
 ///
 /// * Next, this "catches panics". Right now all wasm code is configured as
 ///   panic=abort, but it's more like an exception in JS. It's pretty sketchy
@@ -575,6 +1159,65 @@ impl<F: Future<Error = JsValue>> Future for TestFuture<F> {
     }
 }
 
+/// A wrapper future around asynchronous tests which fails them if they take
+/// too long.
+///
+/// The timer is only started once the test turns out to not finish right
+/// away, and it's cleared as soon as the test finishes so it doesn't keep
+/// node.js running.
+struct Timeout<F> {
+    test: F,
+    ms: u32,
+    timer: Option<Timer>,
+}
+
+/// A `setTimeout` timer along with a future which resolves once it fires.
+struct Timer {
+    id: JsValue,
+    fired: JsFuture,
+}
+
+impl<F: Future<Item = (), Error = JsValue>> Future for Timeout<F> {
+    type Item = ();
+    type Error = JsValue;
+
+    fn poll(&mut self) -> Poll<(), JsValue> {
+        match self.test.poll() {
+            Ok(Async::NotReady) => {}
+            other => {
+                self.timer = None;
+                return other;
+            }
+        }
+
+        if self.timer.is_none() {
+            let ms = self.ms;
+            let mut id = JsValue::undefined();
+            let promise = Promise::new(&mut |resolve, _reject| {
+                id = set_timeout(&resolve, ms);
+            });
+            self.timer = Some(Timer {
+                id,
+                fired: JsFuture::from(promise),
+            });
+        }
+        match self.timer.as_mut().unwrap().fired.poll() {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            _ => {
+                self.timer = None;
+                let msg = format!("test timed out after {}ms", self.ms);
+                Err(js_sys::Error::new(&msg).into())
+            }
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        clear_timeout(&self.id);
+    }
+}
+
 fn tab(s: &str) -> String {
     let mut result = String::new();
     for line in s.lines() {
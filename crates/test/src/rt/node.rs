@@ -38,6 +38,10 @@ impl super::Formatter for Node {
         self.writeln(&format!("test {} ... {}", name, s));
     }
 
+    fn log_ignored(&self, name: &str) {
+        self.writeln(&format!("test {} ... ignored", name));
+    }
+
     fn stringify_error(&self, err: &JsValue) -> String {
         // TODO: should do a checked cast to `NodeError`
         NodeError::from(err.clone()).stack()
@@ -0,0 +1,321 @@
+//! Structured reports of a test run, for consumption by CI systems.
+//!
+//! With `--format json` or `--format junit` the results of all tests are
+//! collected here, alongside the usual output of the `Formatter`. Once the
+//! tests have finished the report is rendered into a string which
+//! `wasm-bindgen-test-runner` writes to the file given with `--logfile`.
+//!
+//! The JSON format follows the events printed by libtest with
+//! `--format json`, one JSON object per line, and the JUnit format follows the
+//! XML printed by libtest with `--format junit`.
+
+use std::fmt::Write;
+
+use super::bench::Summary;
+
+/// The formats a report can be rendered in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Junit,
+}
+
+/// The results of a test run, recorded as tests finish.
+pub struct Report {
+    format: Format,
+    tests: Vec<TestReport>,
+}
+
+struct TestReport {
+    name: String,
+    outcome: Outcome,
+    /// How long the test took to execute, in seconds.
+    exec_time: f64,
+    /// Captured output of `console.debug`, `console.log` and `console.info`.
+    stdout: String,
+    /// Captured output of `console.warn` and `console.error`.
+    stderr: String,
+}
+
+enum Outcome {
+    Ok,
+    Failed(String),
+    Ignored,
+    Bench(Summary),
+}
+
+impl Report {
+    pub fn new(format: Format) -> Report {
+        Report {
+            format,
+            tests: Vec::new(),
+        }
+    }
+
+    /// Records that a test passed, or failed with the stringified `error`,
+    /// along with its captured console output.
+    pub fn test(
+        &mut self,
+        name: &str,
+        error: Option<String>,
+        exec_time: f64,
+        stdout: String,
+        stderr: String,
+    ) {
+        let outcome = match error {
+            Some(error) => Outcome::Failed(error),
+            None => Outcome::Ok,
+        };
+        self.tests.push(TestReport {
+            name: name.to_string(),
+            outcome,
+            exec_time,
+            stdout,
+            stderr,
+        });
+    }
+
+    /// Records that a test was ignored due to `#[ignore]`.
+    pub fn ignored(&mut self, name: &str) {
+        self.tests.push(TestReport {
+            name: name.to_string(),
+            outcome: Outcome::Ignored,
+            exec_time: 0.0,
+            stdout: String::new(),
+            stderr: String::new(),
+        });
+    }
+
+    /// Records the statistics of a benchmark which was measured with `--bench`.
+    pub fn bench(&mut self, name: &str, exec_time: f64, summary: &Summary) {
+        self.tests.push(TestReport {
+            name: name.to_string(),
+            outcome: Outcome::Bench(*summary),
+            exec_time,
+            stdout: String::new(),
+            stderr: String::new(),
+        });
+    }
+
+    /// Renders the report, given the number of tests which were filtered out
+    /// and how long the whole test run took in seconds.
+    pub fn render(&self, filtered_out: usize, exec_time: f64) -> String {
+        match self.format {
+            Format::Json => self.render_json(filtered_out, exec_time),
+            Format::Junit => self.render_junit(exec_time),
+        }
+    }
+
+    /// Returns the number of tests ignored due to `#[ignore]`.
+    pub fn ignored_count(&self) -> usize {
+        self.count(|o| match o {
+            Outcome::Ignored => true,
+            _ => false,
+        })
+    }
+
+    fn count(&self, f: impl Fn(&Outcome) -> bool) -> usize {
+        self.tests.iter().filter(|t| f(&t.outcome)).count()
+    }
+
+    fn render_json(&self, filtered_out: usize, exec_time: f64) -> String {
+        let mut dst = String::new();
+        writeln!(
+            dst,
+            r#"{{ "type": "suite", "event": "started", "test_count": {} }}"#,
+            self.tests.len()
+        )
+        .unwrap();
+        for test in self.tests.iter() {
+            let name = json_string(&test.name);
+            writeln!(
+                dst,
+                r#"{{ "type": "test", "event": "started", "name": {} }}"#,
+                name
+            )
+            .unwrap();
+            // Like libtest the result of a benchmark is a separate type of
+            // event, which also includes the mean time per iteration so the
+            // report can be used as a baseline for later runs.
+            if let Outcome::Bench(summary) = &test.outcome {
+                write!(
+                    dst,
+                    r#"{{ "type": "bench", "name": {}, "median": {}, "deviation": {}, "mean": {}"#,
+                    name, summary.median, summary.std_dev, summary.mean
+                )
+                .unwrap();
+                if let Some(mb_s) = summary.mb_s {
+                    write!(dst, r#", "mib_per_second": {}"#, mb_s).unwrap();
+                }
+                dst.push_str(" }\n");
+                continue;
+            }
+            let event = match &test.outcome {
+                Outcome::Ok => "ok",
+                Outcome::Failed(_) => "failed",
+                Outcome::Ignored => "ignored",
+                Outcome::Bench(_) => unreachable!(),
+            };
+            write!(
+                dst,
+                r#"{{ "type": "test", "name": {}, "event": "{}""#,
+                name, event
+            )
+            .unwrap();
+            if let Outcome::Ignored = test.outcome {
+                dst.push_str(" }\n");
+                continue;
+            }
+            write!(dst, r#", "exec_time": {}"#, test.exec_time).unwrap();
+            if let Outcome::Failed(error) = &test.outcome {
+                write!(dst, r#", "message": {}"#, json_string(error)).unwrap();
+            }
+            if !test.stdout.is_empty() {
+                write!(dst, r#", "stdout": {}"#, json_string(&test.stdout)).unwrap();
+            }
+            if !test.stderr.is_empty() {
+                write!(dst, r#", "stderr": {}"#, json_string(&test.stderr)).unwrap();
+            }
+            dst.push_str(" }\n");
+        }
+        let failed = self.count(|o| match o {
+            Outcome::Failed(_) => true,
+            _ => false,
+        });
+        writeln!(
+            dst,
+            r#"{{ "type": "suite", "event": "{}", "passed": {}, "failed": {}, "ignored": {}, "measured": {}, "filtered_out": {}, "exec_time": {} }}"#,
+            if failed == 0 { "ok" } else { "failed" },
+            self.count(|o| match o {
+                Outcome::Ok => true,
+                _ => false,
+            }),
+            failed,
+            self.ignored_count(),
+            self.count(|o| match o {
+                Outcome::Bench(_) => true,
+                _ => false,
+            }),
+            filtered_out,
+            exec_time,
+        )
+        .unwrap();
+        dst
+    }
+
+    fn render_junit(&self, exec_time: f64) -> String {
+        let mut dst = String::new();
+        dst.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        dst.push('\n');
+        writeln!(
+            dst,
+            r#"<testsuites><testsuite name="test" package="test" id="0" errors="0" failures="{}" tests="{}" skipped="{}" time="{}">"#,
+            self.count(|o| match o {
+                Outcome::Failed(_) => true,
+                _ => false,
+            }),
+            self.tests.len(),
+            self.ignored_count(),
+            exec_time,
+        )
+        .unwrap();
+        for test in self.tests.iter() {
+            // Like libtest the module path of a test is its class name.
+            let (classname, name) = match test.name.rfind("::") {
+                Some(i) => (&test.name[..i], &test.name[i + 2..]),
+                None => ("", &test.name[..]),
+            };
+            write!(
+                dst,
+                r#"<testcase classname="{}" name="{}" time="{}">"#,
+                xml_escape(classname),
+                xml_escape(name),
+                test.exec_time,
+            )
+            .unwrap();
+            match &test.outcome {
+                Outcome::Ok | Outcome::Bench(_) => {}
+                Outcome::Failed(error) => {
+                    let message = error.lines().next().unwrap_or("");
+                    write!(
+                        dst,
+                        r#"<failure type="assert" message="{}">{}</failure>"#,
+                        xml_escape(message),
+                        xml_escape(error),
+                    )
+                    .unwrap();
+                }
+                Outcome::Ignored => dst.push_str("<skipped/>"),
+            }
+            if !test.stdout.is_empty() {
+                write!(dst, "<system-out>{}</system-out>", xml_escape(&test.stdout)).unwrap();
+            }
+            if !test.stderr.is_empty() {
+                write!(dst, "<system-err>{}</system-err>", xml_escape(&test.stderr)).unwrap();
+            }
+            dst.push_str("</testcase>\n");
+        }
+        dst.push_str("</testsuite></testsuites>\n");
+        dst
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut dst = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => dst.push_str("\\\""),
+            '\\' => dst.push_str("\\\\"),
+            '\n' => dst.push_str("\\n"),
+            '\r' => dst.push_str("\\r"),
+            '\t' => dst.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(dst, "\\u{:04x}", c as u32).unwrap(),
+            c => dst.push(c),
+        }
+    }
+    dst.push('"');
+    dst
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut dst = String::new();
+    for c in s.chars() {
+        match c {
+            '<' => dst.push_str("&lt;"),
+            '>' => dst.push_str("&gt;"),
+            '&' => dst.push_str("&amp;"),
+            '"' => dst.push_str("&quot;"),
+            '\'' => dst.push_str("&apos;"),
+            '\t' | '\n' | '\r' => dst.push(c),
+            // Other control characters can't appear in XML 1.0 documents at
+            // all, not even as character references, so replace them.
+            c if (c as u32) < 0x20 => dst.push('\u{fffd}'),
+            c => dst.push(c),
+        }
+    }
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_string, xml_escape};
+
+    #[test]
+    fn xml_escape_works() {
+        assert_eq!(xml_escape("a < b && c > d"), "a &lt; b &amp;&amp; c &gt; d");
+        assert_eq!(xml_escape("\"'"), "&quot;&apos;");
+        assert_eq!(xml_escape("a\tb\nc\r\n"), "a\tb\nc\r\n");
+        assert_eq!(
+            xml_escape("\x1b[31mred\x1b[0m"),
+            "\u{fffd}[31mred\u{fffd}[0m"
+        );
+        assert_eq!(xml_escape("\0\x08\x1f"), "\u{fffd}\u{fffd}\u{fffd}");
+        assert_eq!(xml_escape("\x7f é"), "\x7f é");
+    }
+
+    #[test]
+    fn json_string_works() {
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("\n\r\t\x1b"), "\"\\n\\r\\t\\u001b\"");
+    }
+}
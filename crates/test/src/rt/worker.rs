@@ -0,0 +1,47 @@
+//! Support for printing status information of a test suite in a web worker.
+//!
+//! Workers don't have a DOM to render output into, so all output is sent to
+//! the page which started the worker, which renders it the same as
+//! `Browser` does.
+
+use wasm_bindgen::prelude::*;
+
+/// Implementation of `Formatter` for dedicated and shared workers.
+pub struct Worker {}
+
+#[wasm_bindgen]
+extern "C" {
+    // Defined by the worker script of `wasm-bindgen-test-runner`, posting the
+    // line to the page.
+    fn __wbg_test_output_writeln(line: &str);
+}
+
+impl Worker {
+    /// Attempts to create a new formatter for web workers, returning `None`
+    /// if this isn't executing in a worker.
+    pub fn new() -> Option<Worker> {
+        if !super::detect::is_worker() {
+            return None;
+        }
+        Some(Worker {})
+    }
+}
+
+impl super::Formatter for Worker {
+    fn writeln(&self, line: &str) {
+        __wbg_test_output_writeln(line);
+    }
+
+    fn log_test(&self, name: &str, result: &Result<(), JsValue>) {
+        let s = if result.is_ok() { "ok" } else { "FAIL" };
+        self.writeln(&format!("test {} ... {}", name, s));
+    }
+
+    fn log_ignored(&self, name: &str) {
+        self.writeln(&format!("test {} ... ignored", name));
+    }
+
+    fn stringify_error(&self, err: &JsValue) -> String {
+        super::browser::stringify_error(err)
+    }
+}
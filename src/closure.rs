@@ -25,15 +25,23 @@ use crate::UnwrapThrowExt;
 ///
 /// This type is a "handle" in the sense that whenever it is dropped it will
 /// invalidate the JS closure that it refers to. Any usage of the closure in JS
-/// after the `Closure` has been dropped will raise an exception. It's then up
-/// to you to arrange for `Closure` to be properly deallocate at an appropriate
-/// location in your program.
+/// after the `Closure` has been dropped will raise an exception, unless a
+/// different `DropPolicy` is set. It's then up to you to arrange for `Closure`
+/// to be properly deallocate at an appropriate location in your program.
+///
+/// JS can find out when the Rust closure is destroyed by setting the `ondrop`
+/// property of its function to a callback, for example to remove it as an
+/// event listener. The callback is called when the `Closure` is dropped, or
+/// once the closure returns if it's dropped while running, and when a function
+/// from `Closure::into_js_value` is garbage collected, provided the callback
+/// doesn't refer to the function. Exceptions thrown by the callback are logged
+/// with `console.error` since they can't be propagated.
 ///
 /// The type parameter on `Closure` is the type of closure that this represents.
-/// Currently this can only be the `Fn` and `FnMut` traits with up to 7
-/// arguments (and an optional return value). The arguments/return value of the
-/// trait must be numbers like `u32` for now, although this restriction may be
-/// lifted in the future!
+/// Currently this can only be the `Fn` and `FnMut` traits with up to 8
+/// arguments (and an optional return value). The arguments and return value
+/// can be any types that can be shared with JS, and up to two of the
+/// arguments may also be borrowed.
 ///
 /// # Examples
 ///
@@ -245,6 +253,23 @@ pub struct Closure<T: ?Sized> {
     data: ManuallyDrop<Box<T>>,
 }
 
+/// What happens when JS calls a closure whose `Closure` has already been
+/// dropped in Rust.
+///
+/// The policy of a `Closure` is set with `Closure::with_drop_policy`, and
+/// defaults to `DropPolicy::Throw`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Throw an `Error` named `DroppedClosureError` whose message names the
+    /// type of the closure.
+    Throw,
+    /// Do nothing and return `undefined`.
+    Ignore,
+    /// Like `Ignore`, but also log a warning with `console.warn` if debug
+    /// assertions are enabled.
+    Log,
+}
+
 union FatPtr<T: ?Sized> {
     ptr: *mut T,
     fields: (usize, usize),
@@ -280,7 +305,7 @@ where
     /// * It must be `'static`, aka no stack references (use the `move`
     ///   keyword).
     ///
-    /// * It can have at most 7 arguments.
+    /// * It can have at most 8 arguments.
     ///
     /// * Its arguments and return values are all types that can be shared with
     ///   JS (i.e. have `#[wasm_bindgen]` annotations or are simple numbers,
@@ -373,6 +398,64 @@ where
             mem::forget(self);
         }
     }
+
+    /// Sets what happens when JS calls this closure after it's been dropped.
+    ///
+    /// This is useful for callbacks which JS may still call after Rust is done
+    /// with them, for example event listeners which are removed lazily.
+    ///
+    /// ```rust,no_run
+    /// use wasm_bindgen::closure::DropPolicy;
+    /// use wasm_bindgen::prelude::*;
+    ///
+    /// let cb = Closure::wrap(Box::new(|| {
+    ///     // ...
+    /// }) as Box<FnMut()>)
+    /// .with_drop_policy(DropPolicy::Ignore);
+    /// ```
+    pub fn with_drop_policy(self, policy: DropPolicy) -> Closure<T> {
+        // NB: this must be kept in sync with `closureDropped` in the JS glue.
+        let policy = match policy {
+            DropPolicy::Throw => 0,
+            DropPolicy::Log if cfg!(debug_assertions) => 2,
+            DropPolicy::Ignore | DropPolicy::Log => 1,
+        };
+        if policy != 0 {
+            unsafe {
+                super::__wbindgen_cb_set_drop_policy(self.js.idx, policy);
+            }
+        }
+        self
+    }
+
+    /// Converts this `Closure` into the JS function it refers to, handing
+    /// ownership of the Rust closure over to JS.
+    ///
+    /// The returned JS function stays valid for as long as JS references it.
+    /// When the JS bindings are generated with weak references enabled (the
+    /// `--weak-refs` flag of the `wasm-bindgen` CLI) the Rust closure and
+    /// everything it closes over is dropped once the JS function is garbage
+    /// collected. Otherwise, just like with `Closure::forget`, the Rust closure
+    /// is leaked.
+    ///
+    /// ```rust,no_run
+    /// use wasm_bindgen::prelude::*;
+    ///
+    /// #[wasm_bindgen]
+    /// extern "C" {
+    ///     fn setTimeout(callback: JsValue, millis: u32);
+    /// }
+    ///
+    /// let cb = Closure::wrap(Box::new(|| {
+    ///     // ...
+    /// }) as Box<FnMut()>);
+    /// setTimeout(cb.into_js_value(), 1_000);
+    /// ```
+    pub fn into_js_value(self) -> JsValue {
+        let idx = self.js.idx;
+        mem::forget(self);
+        JsValue::_new(idx)
+    }
 }
 
 // NB: we use a specific `T` for this `Closure<T>` impl block to avoid every
@@ -440,6 +523,26 @@ impl Closure<dyn FnOnce()> {
     {
         fn_once.into_js_function()
     }
+
+    /// Returns whether `js` is a JS function created from a `Closure` which
+    /// can still be called, that is one whose `Closure` hasn't been dropped.
+    ///
+    /// Functions created with `Closure::forget` and `Closure::into_js_value`
+    /// are alive until they're garbage collected. An `FnMut` closure isn't
+    /// alive while it's running, since it can't be called recursively.
+    ///
+    /// ```rust,no_run
+    /// use wasm_bindgen::prelude::*;
+    ///
+    /// let cb = Closure::wrap(Box::new(|| {}) as Box<Fn()>);
+    /// let f = cb.as_ref().clone();
+    /// assert!(Closure::is_alive(&f));
+    /// drop(cb);
+    /// assert!(!Closure::is_alive(&f));
+    /// ```
+    pub fn is_alive(js: &JsValue) -> bool {
+        unsafe { super::__wbindgen_cb_is_alive(js.idx) == 1 }
+    }
 }
 
 /// A trait for converting an `FnOnce(A...) -> R` into a `FnMut(A...) -> R` that
@@ -537,20 +640,24 @@ pub unsafe trait WasmClosure {
 // then destruction is deferred until execution returns. Otherwise it'll
 // deallocate data immediately.
 
-macro_rules! doit {
-    ($(
-        ($($var:ident)*)
-    )*) => ($(
-        unsafe impl<$($var,)* R> WasmClosure for dyn Fn($($var),*) -> R + 'static
-            where $($var: FromWasmAbi + 'static,)*
+macro_rules! closures {
+    ($(($var:ident
+        [$($ty:tt)*]
+        [$($bound:tt)*]
+        [$($abi:tt)*]
+        $from_abi:ident
+        [$($arg:tt)*]
+        [$($once:tt)*]))*) => (
+        unsafe impl<$($var,)* R> WasmClosure for dyn Fn($($($ty)*),*) -> R + 'static
+            where $($var: $($bound)* + 'static,)*
                   R: ReturnWasmAbi + 'static,
         {
             fn describe() {
                 #[allow(non_snake_case)]
-                unsafe extern "C" fn invoke<$($var: FromWasmAbi,)* R: ReturnWasmAbi>(
+                unsafe extern "C" fn invoke<$($var: $($bound)*,)* R: ReturnWasmAbi>(
                     a: usize,
                     b: usize,
-                    $($var: <$var as FromWasmAbi>::Abi),*
+                    $($var: <$var as $($abi)*>::Abi),*
                 ) -> <R as ReturnWasmAbi>::Abi {
                     if a == 0 {
                         throw_str("closure invoked recursively or destroyed already");
@@ -559,20 +666,20 @@ macro_rules! doit {
                     // convert `ret` as it may throw (for `Result`, for
                     // example)
                     let ret = {
-                        let f: *const dyn Fn($($var),*) -> R =
+                        let f: *const dyn Fn($($($ty)*),*) -> R =
                             FatPtr { fields: (a, b) }.ptr;
                         let mut _stack = GlobalStack::new();
                         $(
-                            let $var = <$var as FromWasmAbi>::from_abi($var, &mut _stack);
+                            let $var = <$var as $($abi)*>::$from_abi($var, &mut _stack);
                         )*
-                        (*f)($($var),*)
+                        (*f)($($($arg)*),*)
                     };
                     ret.return_abi(&mut GlobalStack::new())
                 }
 
                 inform(invoke::<$($var,)* R> as u32);
 
-                unsafe extern fn destroy<$($var: FromWasmAbi,)* R: ReturnWasmAbi>(
+                unsafe extern "C" fn destroy<$($var: $($bound)*,)* R: ReturnWasmAbi>(
                     a: usize,
                     b: usize,
                 ) {
@@ -584,7 +691,7 @@ macro_rules! doit {
                     if a == 0 {
                         return;
                     }
-                    drop(Box::from_raw(FatPtr::<dyn Fn($($var,)*) -> R> {
+                    drop(Box::from_raw(FatPtr::<dyn Fn($($($ty)*),*) -> R> {
                         fields: (a, b)
                     }.ptr));
                 }
@@ -594,16 +701,16 @@ macro_rules! doit {
             }
         }
 
-        unsafe impl<$($var,)* R> WasmClosure for dyn FnMut($($var),*) -> R + 'static
-            where $($var: FromWasmAbi + 'static,)*
+        unsafe impl<$($var,)* R> WasmClosure for dyn FnMut($($($ty)*),*) -> R + 'static
+            where $($var: $($bound)* + 'static,)*
                   R: ReturnWasmAbi + 'static,
         {
             fn describe() {
                 #[allow(non_snake_case)]
-                unsafe extern "C" fn invoke<$($var: FromWasmAbi,)* R: ReturnWasmAbi>(
+                unsafe extern "C" fn invoke<$($var: $($bound)*,)* R: ReturnWasmAbi>(
                     a: usize,
                     b: usize,
-                    $($var: <$var as FromWasmAbi>::Abi),*
+                    $($var: <$var as $($abi)*>::Abi),*
                 ) -> <R as ReturnWasmAbi>::Abi {
                     if a == 0 {
                         throw_str("closure invoked recursively or destroyed already");
@@ -612,21 +719,21 @@ macro_rules! doit {
                     // convert `ret` as it may throw (for `Result`, for
                     // example)
                     let ret = {
-                        let f: *const dyn FnMut($($var),*) -> R =
+                        let f: *const dyn FnMut($($($ty)*),*) -> R =
                             FatPtr { fields: (a, b) }.ptr;
-                        let f = f as *mut dyn FnMut($($var),*) -> R;
+                        let f = f as *mut dyn FnMut($($($ty)*),*) -> R;
                         let mut _stack = GlobalStack::new();
                         $(
-                            let $var = <$var as FromWasmAbi>::from_abi($var, &mut _stack);
+                            let $var = <$var as $($abi)*>::$from_abi($var, &mut _stack);
                         )*
-                        (*f)($($var),*)
+                        (*f)($($($arg)*),*)
                     };
                     ret.return_abi(&mut GlobalStack::new())
                 }
 
                 inform(invoke::<$($var,)* R> as u32);
 
-                unsafe extern fn destroy<$($var: FromWasmAbi,)* R: ReturnWasmAbi>(
+                unsafe extern "C" fn destroy<$($var: $($bound)*,)* R: ReturnWasmAbi>(
                     a: usize,
                     b: usize,
                 ) {
@@ -634,7 +741,7 @@ macro_rules! doit {
                     if a == 0 {
                         return;
                     }
-                    drop(Box::from_raw(FatPtr::<dyn FnMut($($var,)*) -> R> {
+                    drop(Box::from_raw(FatPtr::<dyn FnMut($($($ty)*),*) -> R> {
                         fields: (a, b)
                     }.ptr));
                 }
@@ -645,16 +752,16 @@ macro_rules! doit {
         }
 
         #[allow(non_snake_case)]
-        impl<T, $($var,)* R> WasmClosureFnOnce<($($var),*), R> for T
-            where T: 'static + FnOnce($($var),*) -> R,
-                  $($var: FromWasmAbi + 'static,)*
+        impl<T, $($var,)* R> WasmClosureFnOnce<($($($once)*,)*), R> for T
+            where T: 'static + FnOnce($($($ty)*),*) -> R,
+                  $($var: $($bound)* + 'static,)*
                   R: ReturnWasmAbi + 'static
         {
-            type FnMut = dyn FnMut($($var),*) -> R;
+            type FnMut = dyn FnMut($($($ty)*),*) -> R;
 
             fn into_fn_mut(self) -> Box<Self::FnMut> {
                 let mut me = Some(self);
-                Box::new(move |$($var),*| {
+                Box::new(move |$($var: $($ty)*),*| {
                     let me = me.take().expect_throw("FnOnce called more than once");
                     me($($var),*)
                 })
@@ -669,7 +776,7 @@ macro_rules! doit {
                 let rc1 = Rc::new(WasmRefCell::new(None));
                 let rc2 = rc1.clone();
 
-                let closure = Closure::wrap(Box::new(move |$($var),*| {
+                let closure = Closure::wrap(Box::new(move |$($var: $($ty)*),*| {
                     // Invoke ourself and get the result.
                     let me = me.take().expect_throw("FnOnce called more than once");
                     let result = me($($var),*);
@@ -682,7 +789,7 @@ macro_rules! doit {
                     drop(option_closure);
 
                     result
-                }) as Box<dyn FnMut($($var),*) -> R>);
+                }) as Box<dyn FnMut($($($ty)*),*) -> R>);
 
                 let js_val = closure.as_ref().clone();
 
@@ -693,168 +800,7 @@ macro_rules! doit {
                 js_val
             }
         }
-    )*)
-}
-
-doit! {
-    ()
-    (A)
-    (A B)
-    (A B C)
-    (A B C D)
-    (A B C D E)
-    (A B C D E F)
-    (A B C D E F G)
-    (A B C D E F G H)
-}
-
-// Copy the above impls down here for where there's only one argument and it's a
-// reference. We could add more impls for more kinds of references, but it
-// becomes a combinatorial explosion quickly. Let's see how far we can get with
-// just this one! Maybe someone else can figure out voodoo so we don't have to
-// duplicate.
-
-unsafe impl<A, R> WasmClosure for dyn Fn(&A) -> R
-    where A: RefFromWasmAbi,
-          R: ReturnWasmAbi + 'static,
-{
-    fn describe() {
-        #[allow(non_snake_case)]
-        unsafe extern "C" fn invoke<A: RefFromWasmAbi, R: ReturnWasmAbi>(
-            a: usize,
-            b: usize,
-            arg: <A as RefFromWasmAbi>::Abi,
-        ) -> <R as ReturnWasmAbi>::Abi {
-            if a == 0 {
-                throw_str("closure invoked recursively or destroyed already");
-            }
-            // Make sure all stack variables are converted before we
-            // convert `ret` as it may throw (for `Result`, for
-            // example)
-            let ret = {
-                let f: *const dyn Fn(&A) -> R =
-                    FatPtr { fields: (a, b) }.ptr;
-                let mut _stack = GlobalStack::new();
-                let arg = <A as RefFromWasmAbi>::ref_from_abi(arg, &mut _stack);
-                (*f)(&*arg)
-            };
-            ret.return_abi(&mut GlobalStack::new())
-        }
-
-        inform(invoke::<A, R> as u32);
-
-        unsafe extern fn destroy<A: RefFromWasmAbi, R: ReturnWasmAbi>(
-            a: usize,
-            b: usize,
-        ) {
-            // See `Fn()` above for why we simply return
-            if a == 0 {
-                return;
-            }
-            drop(Box::from_raw(FatPtr::<dyn Fn(&A) -> R> {
-                fields: (a, b)
-            }.ptr));
-        }
-        inform(destroy::<A, R> as u32);
-
-        <&Self>::describe();
-    }
+    )
 }
 
-unsafe impl<A, R> WasmClosure for dyn FnMut(&A) -> R
-    where A: RefFromWasmAbi,
-          R: ReturnWasmAbi + 'static,
-{
-    fn describe() {
-        #[allow(non_snake_case)]
-        unsafe extern "C" fn invoke<A: RefFromWasmAbi, R: ReturnWasmAbi>(
-            a: usize,
-            b: usize,
-            arg: <A as RefFromWasmAbi>::Abi,
-        ) -> <R as ReturnWasmAbi>::Abi {
-            if a == 0 {
-                throw_str("closure invoked recursively or destroyed already");
-            }
-            // Make sure all stack variables are converted before we
-            // convert `ret` as it may throw (for `Result`, for
-            // example)
-            let ret = {
-                let f: *const dyn FnMut(&A) -> R =
-                    FatPtr { fields: (a, b) }.ptr;
-                let f = f as *mut dyn FnMut(&A) -> R;
-                let mut _stack = GlobalStack::new();
-                let arg = <A as RefFromWasmAbi>::ref_from_abi(arg, &mut _stack);
-                (*f)(&*arg)
-            };
-            ret.return_abi(&mut GlobalStack::new())
-        }
-
-        inform(invoke::<A, R> as u32);
-
-        unsafe extern fn destroy<A: RefFromWasmAbi, R: ReturnWasmAbi>(
-            a: usize,
-            b: usize,
-        ) {
-            // See `Fn()` above for why we simply return
-            if a == 0 {
-                return;
-            }
-            drop(Box::from_raw(FatPtr::<dyn FnMut(&A) -> R> {
-                fields: (a, b)
-            }.ptr));
-        }
-        inform(destroy::<A, R> as u32);
-
-        <&mut Self>::describe();
-    }
-}
-
-#[allow(non_snake_case)]
-impl<T, A, R> WasmClosureFnOnce<(&A,), R> for T
-    where T: 'static + FnOnce(&A) -> R,
-          A: RefFromWasmAbi + 'static,
-          R: ReturnWasmAbi + 'static
-{
-    type FnMut = dyn FnMut(&A) -> R;
-
-    fn into_fn_mut(self) -> Box<Self::FnMut> {
-        let mut me = Some(self);
-        Box::new(move |arg| {
-            let me = me.take().expect_throw("FnOnce called more than once");
-            me(arg)
-        })
-    }
-
-    fn into_js_function(self) -> JsValue {
-        use std::rc::Rc;
-        use crate::__rt::WasmRefCell;
-
-        let mut me = Some(self);
-
-        let rc1 = Rc::new(WasmRefCell::new(None));
-        let rc2 = rc1.clone();
-
-        let closure = Closure::wrap(Box::new(move |arg: &A| {
-            // Invoke ourself and get the result.
-            let me = me.take().expect_throw("FnOnce called more than once");
-            let result = me(arg);
-
-            // And then drop the `Rc` holding this function's `Closure`
-            // alive.
-            debug_assert_eq!(Rc::strong_count(&rc2), 1);
-            let option_closure = rc2.borrow_mut().take();
-            debug_assert!(option_closure.is_some());
-            drop(option_closure);
-
-            result
-        }) as Box<dyn FnMut(&A) -> R>);
-
-        let js_val = closure.as_ref().clone();
-
-        *rc1.borrow_mut() = Some(closure);
-        debug_assert_eq!(Rc::strong_count(&rc1), 2);
-        drop(rc1);
-
-        js_val
-    }
-}
+closure_signatures!(closures);
@@ -6,6 +6,7 @@ mod impls;
 mod slices;
 mod traits;
 
+pub use self::closures::Borrowed;
 pub use self::slices::WasmSlice;
 pub use self::traits::*;
 
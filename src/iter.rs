@@ -0,0 +1,195 @@
+//! Support for exposing Rust iterators to JS.
+
+use std::fmt;
+use std::prelude::v1::*;
+use std::rc::Rc;
+
+use crate::__rt::WasmRefCell;
+use crate::closure::Closure;
+use crate::convert::{IntoWasmAbi, Stack};
+use crate::describe::{inform, WasmDescribe, ANYREF};
+use crate::{JsValue, UnwrapThrowExt};
+
+/// A Rust iterator which is passed to JS as an object implementing the
+/// [iterator protocol], so it can be used in `for...of` loops, spread into
+/// arrays, etc.
+///
+/// This makes it possible to return iterators from exported functions and
+/// methods, which otherwise can't return `impl Iterator` types. The items of
+/// the iterator are converted to JS as they're requested, in the same way as
+/// they would be when returned from an exported function.
+///
+/// The iterator is deallocated once JS has consumed all of its items, or
+/// when JS ends the iteration early by calling its `return` method as
+/// `for...of` does when breaking out of a loop. An iterator which is dropped
+/// by JS before then will leak, similarly to `Closure::once_into_js`.
+///
+/// [iterator protocol]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols#The_iterator_protocol
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+/// use wasm_bindgen::JsIterator;
+///
+/// #[wasm_bindgen]
+/// pub fn countdown(from: u32) -> JsIterator<u32> {
+///     JsIterator::new((0..=from).rev())
+/// }
+/// ```
+pub struct JsIterator<T> {
+    iter: Box<dyn Iterator<Item = T>>,
+}
+
+impl<T> JsIterator<T> {
+    /// Wraps `iter` to be passed to JS.
+    pub fn new<I>(iter: I) -> JsIterator<T>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'static,
+    {
+        JsIterator {
+            iter: Box::new(iter.into_iter()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsIterator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JsIterator {{ ... }}")
+    }
+}
+
+impl<T> WasmDescribe for JsIterator<T> {
+    fn describe() {
+        inform(ANYREF);
+    }
+}
+
+impl<T> IntoWasmAbi for JsIterator<T>
+where
+    T: IntoWasmAbi + 'static,
+{
+    type Abi = u32;
+
+    fn into_abi(self, extra: &mut dyn Stack) -> u32 {
+        JsValue::from(self).into_abi(extra)
+    }
+}
+
+struct State<T> {
+    iter: Option<Box<dyn Iterator<Item = T>>>,
+    item: Option<T>,
+    closures: Option<IteratorClosures<T>>,
+}
+
+impl<T> From<JsIterator<T>> for JsValue
+where
+    T: IntoWasmAbi + 'static,
+{
+    fn from(iter: JsIterator<T>) -> JsValue {
+        let state = Rc::new(WasmRefCell::new(State {
+            iter: Some(iter.iter),
+            item: None,
+            closures: None,
+        }));
+
+        let state2 = state.clone();
+        let advance = Closure::wrap(Box::new(move |stop: bool| {
+            let mut state = state2.borrow_mut();
+            if !stop {
+                if let Some(item) = state.iter.as_mut().and_then(|i| i.next()) {
+                    state.item = Some(item);
+                    return JsValue::TRUE;
+                }
+            }
+
+            // Iteration is over, so drop the iterator along with the
+            // `Closure`s driving it, which also breaks their reference cycle
+            // with `state`.
+            state.iter = None;
+            let closures = state.closures.take();
+            drop(state);
+            drop(closures);
+            JsValue::FALSE
+        }) as Box<dyn FnMut(bool) -> JsValue>);
+
+        let state2 = state.clone();
+        let take = Closure::wrap(Box::new(move || {
+            state2
+                .borrow_mut()
+                .item
+                .take()
+                .expect_throw("iterator item taken more than once")
+        }) as Box<dyn FnMut() -> T>);
+
+        let ret = __make_iterator(advance.as_ref(), take.as_ref(), false);
+        state.borrow_mut().closures = Some(IteratorClosures { advance, take });
+        ret
+    }
+}
+
+/// The `Closure`s backing an iterator created by `__make_iterator`.
+#[doc(hidden)]
+pub struct IteratorClosures<T> {
+    pub advance: Closure<dyn FnMut(bool) -> JsValue>,
+    pub take: Closure<dyn FnMut() -> T>,
+}
+
+/// Creates a JS object implementing the (async) iterator protocol from two
+/// functions.
+///
+/// The `advance` function is called with `false` to move on to the next item
+/// and returns whether there is one, or a promise for that if `is_async`.
+/// When there is, `take` is called to get the item. Once there are no items
+/// left, or when `advance` is called with `true` to end iteration early,
+/// neither function is called again.
+///
+/// For async iterators calls are serialized, so `advance` isn't called again
+/// before its previous promise has settled.
+#[doc(hidden)]
+pub fn __make_iterator(advance: &JsValue, take: &JsValue, is_async: bool) -> JsValue {
+    unsafe {
+        JsValue::_new(super::__wbindgen_iterator_new(
+            advance.idx,
+            take.idx,
+            is_async as u32,
+        ))
+    }
+}
+
+/// Converts a Rust iterator of values which can be converted into `JsValue`
+/// into a JS iterator object, for example to return it from a
+/// `#[wasm_bindgen(iterator)]` method so instances of a class can be used in
+/// `for...of` loops.
+///
+/// This is a shorthand for converting a `JsIterator` of the items converted
+/// into `JsValue`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+///
+/// #[wasm_bindgen]
+/// pub struct Range {
+///     start: u32,
+///     end: u32,
+/// }
+///
+/// #[wasm_bindgen]
+/// impl Range {
+///     #[wasm_bindgen(iterator)]
+///     pub fn iter(&self) -> JsValue {
+///         wasm_bindgen::iter::into_js_iterator(self.start..self.end)
+///     }
+/// }
+/// ```
+pub fn into_js_iterator<I>(iter: I) -> JsValue
+where
+    I: IntoIterator,
+    I::IntoIter: 'static,
+    I::Item: Into<JsValue> + 'static,
+{
+    JsIterator::new(iter.into_iter().map(Into::into)).into()
+}
@@ -12,7 +12,7 @@
 use core::fmt;
 use core::marker;
 use core::mem;
-use core::ops::{Deref, DerefMut};
+use core::ops::{self, Deref, DerefMut};
 
 use crate::convert::FromWasmAbi;
 
@@ -40,6 +40,66 @@ macro_rules! externs {
     )
 }
 
+/// Invokes `$m!` once for every closure signature which can be passed to JS:
+/// up to eight arguments, each of which is either owned (`A`) or borrowed
+/// (`&A`), with at most two borrowed arguments.
+///
+/// Every signature needs its own impls, and allowing any number of borrowed
+/// arguments would take 511 signatures, which makes coherence checking of
+/// this crate take minutes. Two borrowed arguments in any position need 129.
+///
+/// Each argument is described to `$m!` as
+/// `($var [type] [bounds] [abi trait] from_abi_method [value passed] [once marker])`,
+/// where the "once marker" is how the argument appears in the tuple of
+/// argument types of `WasmClosureFnOnce`. Borrowed arguments are wrapped in a
+/// marker type there so the impls for owned and borrowed arguments can't
+/// overlap.
+macro_rules! closure_signatures {
+    ($m:ident) => {
+        closure_signatures!(@args $m [] [* *]);
+        closure_signatures!(@args $m [] [* *] A);
+        closure_signatures!(@args $m [] [* *] A B);
+        closure_signatures!(@args $m [] [* *] A B C);
+        closure_signatures!(@args $m [] [* *] A B C D);
+        closure_signatures!(@args $m [] [* *] A B C D E);
+        closure_signatures!(@args $m [] [* *] A B C D E F);
+        closure_signatures!(@args $m [] [* *] A B C D E F G);
+        closure_signatures!(@args $m [] [* *] A B C D E F G H);
+    };
+    // The second list holds a `*` for every argument which may still be
+    // borrowed.
+    (@args $m:ident [$($done:tt)*] [$($borrows:tt)*]) => {
+        $m!($($done)*);
+    };
+    (@args $m:ident [$($done:tt)*] [$($borrows:tt)*] $var:ident $($rest:ident)*) => {
+        closure_signatures!(@owned $m [$($done)*] [$($borrows)*] $var $($rest)*);
+        closure_signatures!(@borrowed $m [$($done)*] [$($borrows)*] $var $($rest)*);
+    };
+    (@owned $m:ident [$($done:tt)*] [$($borrows:tt)*] $var:ident $($rest:ident)*) => {
+        closure_signatures!(@args $m [$($done)* (
+            $var
+            [$var]
+            [crate::convert::FromWasmAbi]
+            [crate::convert::FromWasmAbi]
+            from_abi
+            [$var]
+            [$var]
+        )] [$($borrows)*] $($rest)*);
+    };
+    (@borrowed $m:ident [$($done:tt)*] [] $($rest:ident)*) => {};
+    (@borrowed $m:ident [$($done:tt)*] [* $($borrows:tt)*] $var:ident $($rest:ident)*) => {
+        closure_signatures!(@args $m [$($done)* (
+            $var
+            [&$var]
+            [crate::convert::RefFromWasmAbi + ?Sized]
+            [crate::convert::RefFromWasmAbi]
+            ref_from_abi
+            [&*$var]
+            [crate::convert::Borrowed<$var>]
+        )] [$($borrows)*] $($rest)*);
+    };
+}
+
 /// A module which is typically glob imported from:
 ///
 /// ```
@@ -63,13 +123,23 @@ pub mod describe;
 mod cast;
 pub use crate::cast::JsCast;
 
+mod try_from;
+pub use crate::try_from::{TryFromJsValue, TryFromJsValueError};
+
 if_std! {
     extern crate std;
     use std::prelude::v1::*;
     pub mod closure;
+    pub mod iter;
+    pub use crate::iter::JsIterator;
     mod anyref;
+    mod send;
+    pub use crate::send::{thread_heap_stats, SendJsValue, ThreadHeapStats};
 }
 
+#[cfg(feature = "serde-serialize")]
+pub mod serde_direct;
+
 /// Representation of an object owned by JS.
 ///
 /// A `JsValue` doesn't actually live in Rust right now but actually in a table
@@ -231,6 +301,49 @@ impl JsValue {
         }
     }
 
+    /// Creates a new `JsValue` from the object `t` provided by converting it
+    /// into JS values directly, without going through JSON.
+    ///
+    /// This function uses a `serde_direct::Serializer` with the default
+    /// configuration, under which maps become `Map`s, byte buffers become
+    /// `Uint8Array`s and `None` becomes `undefined`. See the `serde_direct`
+    /// module for the full mapping and how to change it.
+    ///
+    /// Usage of this API requires activating the `serde-serialize` feature of
+    /// the `wasm-bindgen` crate.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported by the `Serialize` implementation of `T`.
+    #[cfg(feature = "serde-serialize")]
+    pub fn from_serde_direct<T>(t: &T) -> Result<JsValue, serde_direct::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        t.serialize(&serde_direct::Serializer::new())
+    }
+
+    /// Converts this JS value directly into an arbitrary Rust value, without
+    /// going through JSON.
+    ///
+    /// This accepts all the values `JsValue::from_serde_direct` can produce,
+    /// as well as objects in place of `Map`s and arrays of numbers in place
+    /// of `Uint8Array`s.
+    ///
+    /// Usage of this API requires activating the `serde-serialize` feature of
+    /// the `wasm-bindgen` crate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this value doesn't have the shape `T` expects.
+    #[cfg(feature = "serde-serialize")]
+    pub fn into_serde_direct<T>(&self) -> Result<T, serde_direct::Error>
+    where
+        T: for<'a> serde::de::Deserialize<'a>,
+    {
+        T::deserialize(serde_direct::Deserializer::from(self.clone()))
+    }
+
     /// Returns the `f64` value of this JS value if it's an instance of a
     /// number.
     ///
@@ -332,6 +445,79 @@ impl JsValue {
         unsafe { __wbindgen_is_function(self.idx) == 1 }
     }
 
+    /// Tests whether this JS value is an array, as `Array.isArray(self)`.
+    #[inline]
+    pub fn is_array(&self) -> bool {
+        unsafe { __wbindgen_is_array(self.idx) == 1 }
+    }
+
+    /// Tests whether the type of this JS value is `bigint`.
+    #[inline]
+    pub fn is_bigint(&self) -> bool {
+        unsafe { __wbindgen_is_bigint(self.idx) == 1 }
+    }
+
+    /// Returns the JS string produced by `typeof self`, such as `"number"` or
+    /// `"object"`.
+    #[inline]
+    pub fn js_typeof(&self) -> JsValue {
+        unsafe { JsValue::_new(__wbindgen_typeof(self.idx)) }
+    }
+
+    /// Tests whether `self == other` with JS's loose equality, which converts
+    /// values of different types before comparing them.
+    ///
+    /// This is unlike the `PartialEq` implementation of `JsValue`, which uses
+    /// the strict `===` operator.
+    #[inline]
+    pub fn loose_eq(&self, other: &JsValue) -> bool {
+        unsafe { __wbindgen_jsval_loose_eq(self.idx, other.idx) != 0 }
+    }
+
+    /// Tests whether `self in obj`, that is whether `obj` has a property
+    /// named `self`, either itself or through its prototype chain.
+    ///
+    /// Like the `in` operator, this throws a JS `TypeError` if `obj` isn't an
+    /// object.
+    #[inline]
+    pub fn js_in(&self, obj: &JsValue) -> bool {
+        unsafe { __wbindgen_in(self.idx, obj.idx) == 1 }
+    }
+
+    /// Tests whether `self instanceof ctor`, that is whether the prototype of
+    /// `ctor` is in the prototype chain of `self`.
+    ///
+    /// Unlike `JsCast::is_instance_of`, the constructor is a value known only
+    /// at runtime. Like the `instanceof` operator, this throws a JS
+    /// `TypeError` if `ctor` isn't callable.
+    #[inline]
+    pub fn js_instanceof(&self, ctor: &JsValue) -> bool {
+        unsafe { __wbindgen_instanceof(self.idx, ctor.idx) == 1 }
+    }
+
+    /// Tests whether this JS value is falsy, that is whether `!self` is
+    /// `true`.
+    #[inline]
+    pub fn is_falsy(&self) -> bool {
+        unsafe { __wbindgen_is_falsy(self.idx) == 1 }
+    }
+
+    /// Tests whether this JS value is truthy, that is whether `!!self` is
+    /// `true`.
+    #[inline]
+    pub fn is_truthy(&self) -> bool {
+        !self.is_falsy()
+    }
+
+    /// Computes `self ** rhs` with the JS exponentiation operator.
+    ///
+    /// The other arithmetic and bitwise JS operators are available through
+    /// the `std::ops` traits implemented for `JsValue`.
+    #[inline]
+    pub fn pow(&self, rhs: &JsValue) -> JsValue {
+        unsafe { JsValue::_new(__wbindgen_pow(self.idx, rhs.idx)) }
+    }
+
     /// Get a string representation of the JavaScript object for debugging
     #[cfg(feature = "std")]
     fn as_debug_string(&self) -> String {
@@ -387,6 +573,74 @@ if_std! {
     }
 }
 
+// Operators on `JsValue`s have the semantics of the corresponding JS
+// operators, including converting operands of different types. Like in JS
+// they throw a `TypeError` when mixing `BigInt`s with other numbers.
+macro_rules! binary_ops {
+    ($($(#[$attr:meta])* $t:ident $f:ident $intrinsic:ident,)*) => ($(
+        $(#[$attr])*
+        impl<'a, 'b> ops::$t<&'b JsValue> for &'a JsValue {
+            type Output = JsValue;
+
+            #[inline]
+            fn $f(self, rhs: &'b JsValue) -> JsValue {
+                unsafe { JsValue::_new($intrinsic(self.idx, rhs.idx)) }
+            }
+        }
+
+        impl<'a> ops::$t<JsValue> for &'a JsValue {
+            type Output = JsValue;
+
+            #[inline]
+            fn $f(self, rhs: JsValue) -> JsValue {
+                ops::$t::$f(self, &rhs)
+            }
+        }
+
+        impl<'b> ops::$t<&'b JsValue> for JsValue {
+            type Output = JsValue;
+
+            #[inline]
+            fn $f(self, rhs: &'b JsValue) -> JsValue {
+                ops::$t::$f(&self, rhs)
+            }
+        }
+
+        impl ops::$t<JsValue> for JsValue {
+            type Output = JsValue;
+
+            #[inline]
+            fn $f(self, rhs: JsValue) -> JsValue {
+                ops::$t::$f(&self, &rhs)
+            }
+        }
+    )*)
+}
+
+binary_ops! {
+    /// Applies the JS `+` operator, which adds numbers and concatenates
+    /// strings.
+    Add add __wbindgen_add,
+    /// Applies the JS `-` operator.
+    Sub sub __wbindgen_sub,
+    /// Applies the JS `*` operator.
+    Mul mul __wbindgen_mul,
+    /// Applies the JS `/` operator.
+    Div div __wbindgen_div,
+    /// Applies the JS `%` operator.
+    Rem rem __wbindgen_rem,
+    /// Applies the JS `&` operator.
+    BitAnd bitand __wbindgen_bit_and,
+    /// Applies the JS `|` operator.
+    BitOr bitor __wbindgen_bit_or,
+    /// Applies the JS `^` operator.
+    BitXor bitxor __wbindgen_bit_xor,
+    /// Applies the JS `<<` operator.
+    Shl shl __wbindgen_shl,
+    /// Applies the JS `>>` operator, a sign-propagating right shift.
+    Shr shr __wbindgen_shr,
+}
+
 impl<'a> From<&'a str> for JsValue {
     #[inline]
     fn from(s: &'a str) -> JsValue {
@@ -483,6 +737,83 @@ macro_rules! numbers {
 
 numbers! { i8 u8 i16 u16 i32 u32 f32 f64 }
 
+impl JsValue {
+    /// Returns the two 64-bit halves, low half first, of this JS value if it's
+    /// a `BigInt` which fits in a signed or unsigned 128-bit integer.
+    fn bigint_halves(&self, signed: bool) -> Option<[u64; 2]> {
+        let mut halves = [0; 2];
+        unsafe {
+            let ok = __wbindgen_bigint_get_as_i128(
+                self.idx,
+                signed as u32,
+                halves.as_mut_ptr(),
+                halves.len(),
+            );
+            if ok == 1 {
+                Some(halves)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn as_i128(&self) -> Option<i128> {
+        self.bigint_halves(true)
+            .map(|h| ((h[1] as i64 as i128) << 64) | h[0] as i128)
+    }
+
+    fn as_u128(&self) -> Option<u128> {
+        self.bigint_halves(false)
+            .map(|h| ((h[1] as u128) << 64) | h[0] as u128)
+    }
+}
+
+impl From<i128> for JsValue {
+    #[inline]
+    fn from(n: i128) -> JsValue {
+        let lo = n as u64;
+        let hi = (n >> 64) as u64;
+        unsafe {
+            JsValue::_new(__wbindgen_bigint_from_i128(
+                lo as u32,
+                (lo >> 32) as u32,
+                hi as u32,
+                (hi >> 32) as u32,
+            ))
+        }
+    }
+}
+
+impl From<u128> for JsValue {
+    #[inline]
+    fn from(n: u128) -> JsValue {
+        let lo = n as u64;
+        let hi = (n >> 64) as u64;
+        unsafe {
+            JsValue::_new(__wbindgen_bigint_from_u128(
+                lo as u32,
+                (lo >> 32) as u32,
+                hi as u32,
+                (hi >> 32) as u32,
+            ))
+        }
+    }
+}
+
+impl From<i64> for JsValue {
+    #[inline]
+    fn from(n: i64) -> JsValue {
+        JsValue::from(i128::from(n))
+    }
+}
+
+impl From<u64> for JsValue {
+    #[inline]
+    fn from(n: u64) -> JsValue {
+        JsValue::from(u128::from(n))
+    }
+}
+
 externs! {
     #[link(wasm_import_module = "__wbindgen_placeholder__")]
     extern "C" {
@@ -491,10 +822,13 @@ externs! {
 
         fn __wbindgen_string_new(ptr: *const u8, len: usize) -> u32;
         fn __wbindgen_number_new(f: f64) -> u32;
+        fn __wbindgen_bigint_from_i128(lo_low: u32, lo_high: u32, hi_low: u32, hi_high: u32) -> u32;
+        fn __wbindgen_bigint_from_u128(lo_low: u32, lo_high: u32, hi_low: u32, hi_high: u32) -> u32;
         fn __wbindgen_symbol_named_new(ptr: *const u8, len: usize) -> u32;
         fn __wbindgen_symbol_anonymous_new() -> u32;
 
         fn __wbindgen_anyref_heap_live_count() -> u32;
+        fn __wbindgen_heap_snapshot() -> u32;
 
         fn __wbindgen_is_null(idx: u32) -> u32;
         fn __wbindgen_is_undefined(idx: u32) -> u32;
@@ -502,8 +836,15 @@ externs! {
         fn __wbindgen_is_object(idx: u32) -> u32;
         fn __wbindgen_is_function(idx: u32) -> u32;
         fn __wbindgen_is_string(idx: u32) -> u32;
+        fn __wbindgen_is_array(idx: u32) -> u32;
+        fn __wbindgen_is_bigint(idx: u32) -> u32;
+        fn __wbindgen_is_falsy(idx: u32) -> u32;
+        fn __wbindgen_typeof(idx: u32) -> u32;
+        fn __wbindgen_in(prop: u32, obj: u32) -> u32;
+        fn __wbindgen_instanceof(idx: u32, ctor: u32) -> u32;
 
         fn __wbindgen_number_get(idx: u32, invalid: *mut u8) -> f64;
+        fn __wbindgen_bigint_get_as_i128(idx: u32, signed: u32, ptr: *mut u64, len: usize) -> u32;
         fn __wbindgen_boolean_get(idx: u32) -> u32;
         fn __wbindgen_string_get(idx: u32, len: *mut usize) -> *mut u8;
 
@@ -514,6 +855,15 @@ externs! {
 
         fn __wbindgen_cb_drop(idx: u32) -> u32;
         fn __wbindgen_cb_forget(idx: u32) -> ();
+        fn __wbindgen_cb_set_drop_policy(idx: u32, policy: u32) -> ();
+        fn __wbindgen_cb_is_alive(idx: u32) -> u32;
+
+        fn __wbindgen_array_new() -> u32;
+        fn __wbindgen_array_push(array: u32, value: u32) -> ();
+        fn __wbindgen_array_get(array: u32, index: u32) -> u32;
+        fn __wbindgen_length(idx: u32) -> u32;
+
+        fn __wbindgen_iterator_new(advance: u32, take: u32, is_async: u32) -> u32;
 
         fn __wbindgen_describe(v: u32) -> ();
         fn __wbindgen_describe_closure(a: u32, b: u32, c: u32) -> u32;
@@ -521,6 +871,19 @@ externs! {
         fn __wbindgen_json_parse(ptr: *const u8, len: usize) -> u32;
         fn __wbindgen_json_serialize(ret: *mut [usize; 2], idx: u32) -> ();
         fn __wbindgen_jsval_eq(a: u32, b: u32) -> u32;
+        fn __wbindgen_jsval_loose_eq(a: u32, b: u32) -> u32;
+
+        fn __wbindgen_add(a: u32, b: u32) -> u32;
+        fn __wbindgen_sub(a: u32, b: u32) -> u32;
+        fn __wbindgen_mul(a: u32, b: u32) -> u32;
+        fn __wbindgen_div(a: u32, b: u32) -> u32;
+        fn __wbindgen_rem(a: u32, b: u32) -> u32;
+        fn __wbindgen_pow(a: u32, b: u32) -> u32;
+        fn __wbindgen_bit_and(a: u32, b: u32) -> u32;
+        fn __wbindgen_bit_or(a: u32, b: u32) -> u32;
+        fn __wbindgen_bit_xor(a: u32, b: u32) -> u32;
+        fn __wbindgen_shl(a: u32, b: u32) -> u32;
+        fn __wbindgen_shr(a: u32, b: u32) -> u32;
 
         fn __wbindgen_memory() -> u32;
         fn __wbindgen_module() -> u32;
@@ -705,6 +1068,50 @@ pub fn anyref_heap_live_count() -> u32 {
     unsafe { __wbindgen_anyref_heap_live_count() }
 }
 
+if_std! {
+    /// A place in the program which allocated `JsValue`s that are still alive,
+    /// as returned by `heap_snapshot`.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct HeapSite {
+        /// The JS stack trace of the allocation, one frame per line. Frames of
+        /// wasm functions are named after the Rust functions if the wasm
+        /// module has a names section.
+        pub stack: String,
+        /// The number of live `JsValue`s allocated here.
+        pub count: u32,
+    }
+
+    /// Returns the live `JsValue`s in the heap, grouped by where they were
+    /// allocated, with the sites with the most live values first.
+    ///
+    /// This is useful to track down leaked `JsValue`s: taking a snapshot while
+    /// the program is idle shows which code allocated the values that are
+    /// still around.
+    ///
+    /// Recording allocation sites is expensive, so it's only done when the JS
+    /// bindings are generated with `--heap-snapshot`, or with the
+    /// `WASM_BINDGEN_HEAP_SNAPSHOT` environment variable set. Otherwise, or
+    /// when the anyref feature is enabled, this returns `None`.
+    pub fn heap_snapshot() -> Option<Vec<HeapSite>> {
+        let snapshot = unsafe { JsValue::_new(__wbindgen_heap_snapshot()) };
+        if snapshot.is_undefined() {
+            return None;
+        }
+        let get = |array: &JsValue, i| unsafe { JsValue::_new(__wbindgen_array_get(array.idx, i)) };
+        let len = unsafe { __wbindgen_length(snapshot.idx) };
+        let sites = (0..len)
+            .map(|i| {
+                let site = get(&snapshot, i);
+                HeapSite {
+                    stack: get(&site, 0).as_string().unwrap_or_default(),
+                    count: get(&site, 1).as_f64().unwrap_or(0.0) as u32,
+                }
+            })
+            .collect();
+        Some(sites)
+    }
+}
+
 /// An extension trait for `Option<T>` and `Result<T, E>` for unwraping the `T`
 /// value, or throwing a JS error if it is not available.
 ///
@@ -0,0 +1,196 @@
+//! Moving JS values between threads.
+//!
+//! When wasm is used with threads each thread runs in its own worker, and
+//! each worker has its own JS heap. A `JsValue` only refers to a slot in the
+//! heap of the thread which created it, which is why it isn't `Send`. JS values
+//! can only move between workers by being posted in a message, which copies
+//! them with the structured clone algorithm or moves them if they're listed as
+//! `Transferable`s. The `SendJsValue` type in this module pairs such a message
+//! with a handle which can be sent along with other Rust data.
+
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::prelude::v1::*;
+
+use crate::JsValue;
+
+/// Tags messages created by `SendJsValue::new`.
+const MESSAGE_TAG: &str = "__wbindgen_send_js_value";
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+static NEXT_THREAD_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static THREAD_ID: Cell<Option<u32>> = Cell::new(None);
+    static RECEIVED: RefCell<HashMap<u32, JsValue>> = RefCell::new(HashMap::new());
+}
+
+/// A `Send` handle to a JS value which is on its way to another thread.
+///
+/// Creating a `SendJsValue` also creates a JS message containing the value,
+/// which has to be posted to the worker of the receiving thread, for example
+/// with `Worker.postMessage`. Any `Transferable`s in the value, such as
+/// `ArrayBuffer`s or `MessagePort`s, can be moved rather than copied by listing
+/// them in the transfer list of `postMessage`. On the receiving thread the
+/// message is handed to `SendJsValue::receive`, after which the handle, sent
+/// through Rust, can be turned back into a `JsValue`.
+///
+/// A `SendJsValue` which is dropped before its message is received leaks the
+/// value on the receiving thread, so every message should eventually be
+/// received and every handle turned back into a value.
+///
+/// ```rust,no_run
+/// use wasm_bindgen::prelude::*;
+/// use wasm_bindgen::SendJsValue;
+///
+/// #[wasm_bindgen]
+/// extern "C" {
+///     type Worker;
+///     #[wasm_bindgen(method, js_name = postMessage)]
+///     fn post_message(this: &Worker, message: &JsValue);
+/// }
+///
+/// // On the sending thread.
+/// fn send(worker: &Worker, value: JsValue) -> SendJsValue {
+///     let (handle, message) = SendJsValue::new(value);
+///     worker.post_message(&message);
+///     handle
+/// }
+///
+/// // On the receiving thread, in the worker's `onmessage` handler.
+/// #[wasm_bindgen]
+/// pub fn on_message(data: JsValue) {
+///     SendJsValue::receive(data).unwrap_or_else(|_data| {
+///         // not a `SendJsValue` message, handle it some other way
+///     });
+/// }
+///
+/// // Later, on the receiving thread, once the handle has arrived.
+/// fn received(handle: SendJsValue) -> Option<JsValue> {
+///     handle.try_into_value().ok()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SendJsValue {
+    id: u32,
+    origin: u32,
+}
+
+impl SendJsValue {
+    /// Wraps `value` for sending to another thread, returning the handle and
+    /// the message which has to be posted to the receiving thread.
+    pub fn new(value: JsValue) -> (SendJsValue, JsValue) {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst) as u32;
+        let message = JsValue::_new(unsafe { crate::__wbindgen_array_new() });
+        push(&message, JsValue::from_str(MESSAGE_TAG));
+        push(&message, JsValue::from(id));
+        push(&message, value);
+        let handle = SendJsValue {
+            id,
+            origin: thread_id(),
+        };
+        (handle, message)
+    }
+
+    /// Receives a message created by `SendJsValue::new` on the current thread.
+    ///
+    /// The value in the message can then be taken out with the corresponding
+    /// `SendJsValue` on this thread. If `message` wasn't created by
+    /// `SendJsValue::new` it's handed back as an error.
+    pub fn receive(message: JsValue) -> Result<(), JsValue> {
+        if !message.is_array() || unsafe { crate::__wbindgen_length(message.idx) } != 3 {
+            return Err(message);
+        }
+        if get(&message, 0) != MESSAGE_TAG {
+            return Err(message);
+        }
+        let id = match get(&message, 1).as_f64() {
+            Some(id) => id as u32,
+            None => return Err(message),
+        };
+        let value = get(&message, 2);
+        RECEIVED.with(|received| received.borrow_mut().insert(id, value));
+        Ok(())
+    }
+
+    /// Takes the value out of the message received on the current thread.
+    ///
+    /// If the message hasn't been received on this thread (yet) the handle is
+    /// handed back as an error.
+    pub fn try_into_value(self) -> Result<JsValue, SendJsValue> {
+        match RECEIVED.with(|received| received.borrow_mut().remove(&self.id)) {
+            Some(value) => {
+                mem::forget(self);
+                Ok(value)
+            }
+            None => Err(self),
+        }
+    }
+
+    /// Returns the `thread_heap_stats().thread_id` of the thread which created
+    /// this `SendJsValue`.
+    pub fn origin_thread(&self) -> u32 {
+        self.origin
+    }
+}
+
+// Dropping a `SendJsValue` can only drop the value if its message was
+// received on the current thread. If the message is received after the
+// `SendJsValue` is dropped the value stays alive in the receiving thread's heap
+// for as long as the thread does.
+impl Drop for SendJsValue {
+    fn drop(&mut self) {
+        let id = self.id;
+        RECEIVED.with(|received| received.borrow_mut().remove(&id));
+    }
+}
+
+/// Statistics about the JS heap of the current thread, as returned by
+/// `thread_heap_stats`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ThreadHeapStats {
+    /// An identifier for the current thread, assigned in the order in which
+    /// threads first use `SendJsValue` or `thread_heap_stats`.
+    pub thread_id: u32,
+    /// The number of live `JsValue`s in the heap of this thread, see
+    /// `anyref_heap_live_count`.
+    pub live_count: u32,
+    /// The number of values received with `SendJsValue::receive` on this
+    /// thread which haven't been taken out with their `SendJsValue` yet.
+    pub pending_received: u32,
+}
+
+/// Returns statistics about the JS heap of the current thread.
+///
+/// Every thread has its own JS heap, so this only describes the `JsValue`s
+/// owned by the calling thread. Comparing the statistics of different threads
+/// helps to find out which thread is holding on to JS values.
+pub fn thread_heap_stats() -> ThreadHeapStats {
+    ThreadHeapStats {
+        thread_id: thread_id(),
+        live_count: crate::anyref_heap_live_count(),
+        pending_received: RECEIVED.with(|received| received.borrow().len() as u32),
+    }
+}
+
+fn push(array: &JsValue, value: JsValue) {
+    unsafe { crate::__wbindgen_array_push(array.idx, value.idx) }
+    mem::forget(value);
+}
+
+fn get(array: &JsValue, index: u32) -> JsValue {
+    unsafe { JsValue::_new(crate::__wbindgen_array_get(array.idx, index)) }
+}
+
+fn thread_id() -> u32 {
+    THREAD_ID.with(|id| match id.get() {
+        Some(id) => id,
+        None => {
+            let new = NEXT_THREAD_ID.fetch_add(1, Ordering::SeqCst) as u32;
+            id.set(Some(new));
+            new
+        }
+    })
+}
@@ -0,0 +1,350 @@
+use core::convert::TryFrom;
+use std::prelude::v1::*;
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use super::{Error, MAX_SAFE_INTEGER};
+use crate::JsValue;
+
+/// A Serde deserializer which converts `JsValue`s into Rust values.
+///
+/// See the [module documentation](index.html) for the JS values it accepts.
+/// This is what `JsValue::into_serde_direct` uses.
+#[derive(Debug)]
+pub struct Deserializer {
+    value: JsValue,
+}
+
+impl From<JsValue> for Deserializer {
+    fn from(value: JsValue) -> Deserializer {
+        Deserializer { value }
+    }
+}
+
+impl Deserializer {
+    fn is_missing(&self) -> bool {
+        self.value.is_null() || self.value.is_undefined()
+    }
+
+    fn visit_number<'de, V: Visitor<'de>>(n: f64, visitor: V) -> Result<V::Value, Error> {
+        if n.fract() != 0.0 || n.abs() > MAX_SAFE_INTEGER {
+            visitor.visit_f64(n)
+        } else if n < 0.0 {
+            visitor.visit_i64(n as i64)
+        } else {
+            visitor.visit_u64(n as u64)
+        }
+    }
+
+    fn visit_bigint<'de, V: Visitor<'de>>(&self, visitor: V) -> Result<V::Value, Error> {
+        if let Some(n) = self.value.as_i128() {
+            if let Ok(n) = i64::try_from(n) {
+                visitor.visit_i64(n)
+            } else if let Ok(n) = u64::try_from(n) {
+                visitor.visit_u64(n)
+            } else {
+                visitor.visit_i128(n)
+            }
+        } else if let Some(n) = self.value.as_u128() {
+            visitor.visit_u128(n)
+        } else {
+            Err(de::Error::custom(format_args!(
+                "BigInt {:?} is out of range of 128-bit integers",
+                self.value
+            )))
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_missing() {
+            return visitor.visit_unit();
+        }
+        if let Some(b) = self.value.as_bool() {
+            return visitor.visit_bool(b);
+        }
+        if let Some(n) = self.value.as_f64() {
+            return Deserializer::visit_number(n, visitor);
+        }
+        if self.value.is_bigint() {
+            return self.visit_bigint(visitor);
+        }
+        if let Some(s) = self.value.as_string() {
+            return visitor.visit_string(s);
+        }
+        if self.value.is_array() {
+            let len = super::length(&self.value);
+            return visitor.visit_seq(SeqAccess {
+                array: self.value,
+                index: 0,
+                len,
+            });
+        }
+        if super::is_uint8_array(&self.value) {
+            let bytes = super::uint8_array_to_vec(&self.value);
+            return visitor.visit_seq(de::value::SeqDeserializer::new(bytes.into_iter()));
+        }
+        if self.value.is_object() {
+            return visitor.visit_map(MapAccess::new(&self.value));
+        }
+        Err(de::Error::custom(format_args!(
+            "unsupported JS value: {:?}",
+            self.value
+        )))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_missing() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if super::is_uint8_array(&self.value) {
+            visitor.visit_byte_buf(super::uint8_array_to_vec(&self.value))
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if let Some(s) = self.value.as_string() {
+            return visitor.visit_enum(s.into_deserializer());
+        }
+        if self.value.is_object() && !self.value.is_array() {
+            let entries = super::entries(&self.value);
+            if super::length(&entries) == 1 {
+                let entry = super::array_get(&entries, 0);
+                return visitor.visit_enum(EnumAccess {
+                    variant: super::array_get(&entry, 0),
+                    value: super::array_get(&entry, 1),
+                });
+            }
+        }
+        Err(de::Error::custom(
+            "expected a string or an object with a single property for an enum",
+        ))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+/// Accesses the elements of an array.
+struct SeqAccess {
+    array: JsValue,
+    index: u32,
+    len: u32,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let value = super::array_get(&self.array, self.index);
+        self.index += 1;
+        seed.deserialize(Deserializer::from(value)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+/// Accesses the entries of a `Map` or the properties of a plain object.
+struct MapAccess {
+    entries: JsValue,
+    index: u32,
+    len: u32,
+    value: Option<JsValue>,
+    is_object: bool,
+}
+
+impl MapAccess {
+    fn new(value: &JsValue) -> MapAccess {
+        let entries = super::entries(value);
+        MapAccess {
+            index: 0,
+            len: super::length(&entries),
+            entries,
+            value: None,
+            is_object: !super::is_map(value),
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let entry = super::array_get(&self.entries, self.index);
+        self.index += 1;
+        let key = super::array_get(&entry, 0);
+        self.value = Some(super::array_get(&entry, 1));
+
+        if self.is_object {
+            // Property names of plain objects are always strings, but may
+            // stand for other types of keys such as integers.
+            let key = ObjectKeyDeserializer {
+                key: key.as_string().unwrap_or_default(),
+            };
+            seed.deserialize(key).map(Some)
+        } else {
+            seed.deserialize(Deserializer::from(key)).map(Some)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(Deserializer::from(value)),
+            None => Err(de::Error::custom("map value deserialized before its key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+/// Accesses an enum variant represented as an object with a single property.
+struct EnumAccess {
+    variant: JsValue,
+    value: JsValue,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Deserializer), Error> {
+        let variant = seed.deserialize(Deserializer::from(self.variant))?;
+        Ok((variant, Deserializer::from(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// Deserializes the property name of a plain object, parsing it if a number
+/// or boolean is expected.
+struct ObjectKeyDeserializer {
+    key: String,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => ($(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.key.parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(_) => visitor.visit_string(self.key),
+            }
+        }
+    )*)
+}
+
+impl<'de> de::Deserializer<'de> for ObjectKeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.key)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self.key.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,181 @@
+//! Conversion between Rust values and JS values with [Serde], without going
+//! through JSON.
+//!
+//! Unlike `JsValue::from_serde` and `JsValue::into_serde`, which serialize
+//! values to a JSON string and parse it on the other side, the `Serializer`
+//! and `Deserializer` in this module build and inspect JS objects, arrays,
+//! `Map`s and `Uint8Array`s directly. This avoids pulling in the code to
+//! format and parse JSON, and supports values which JSON can't represent,
+//! such as maps with non-string keys, binary data and large integers.
+//!
+//! Rust values are mapped to JS values as follows:
+//!
+//! * `bool`s, strings and `char`s become JS booleans and strings.
+//! * Numbers become JS numbers. 64 and 128-bit integers which don't fit in a
+//!   JS number without losing precision become `BigInt`s.
+//! * `None`, `()` and unit structs become `undefined`.
+//! * Sequences, tuples and tuple structs become arrays.
+//! * Structs become plain objects.
+//! * Maps become `Map`s.
+//! * Byte buffers (e.g. `serde_bytes::ByteBuf`) become `Uint8Array`s.
+//! * Enums are externally tagged: unit variants become strings and other
+//!   variants become an object with a single property named after the variant.
+//!
+//! Several of these mappings can be changed through the methods on
+//! `Serializer`. The `Deserializer` accepts values in any of the forms the
+//! `Serializer` can produce.
+//!
+//! Usage of this module requires activating the `serde-serialize` feature of
+//! the `wasm-bindgen` crate.
+//!
+//! [Serde]: https://serde.rs
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::collections::HashMap;
+//! use serde::Serialize;
+//! use wasm_bindgen::prelude::*;
+//! use wasm_bindgen::serde_direct::Serializer;
+//!
+//! #[wasm_bindgen]
+//! pub fn scores() -> Result<JsValue, JsValue> {
+//!     let mut scores = HashMap::new();
+//!     scores.insert("alice", 3);
+//!     scores.insert("bob", 5);
+//!
+//!     // Return `{ alice: 3, bob: 5 }` rather than a `Map`.
+//!     let serializer = Serializer::new().serialize_maps_as_objects(true);
+//!     Ok(scores.serialize(&serializer)?)
+//! }
+//! ```
+
+use core::fmt;
+use core::mem;
+use std::prelude::v1::*;
+
+use crate::JsValue;
+
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+externs! {
+    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+    extern "C" {
+        fn __wbindgen_is_map(idx: u32) -> u32;
+        fn __wbindgen_is_uint8_array(idx: u32) -> u32;
+
+        fn __wbindgen_object_new() -> u32;
+        fn __wbindgen_object_set(object: u32, key: u32, value: u32) -> ();
+        fn __wbindgen_map_new() -> u32;
+        fn __wbindgen_map_set(map: u32, key: u32, value: u32) -> ();
+        fn __wbindgen_entries(idx: u32) -> u32;
+
+        fn __wbindgen_uint8_array_new(ptr: *const u8, len: usize) -> u32;
+        fn __wbindgen_uint8_array_copy_to(array: u32, ptr: *mut u8, len: usize) -> ();
+    }
+}
+
+/// The largest integer which JS numbers can represent exactly, along with all
+/// integers smaller than it, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// An error encountered while serializing to or deserializing from a
+/// `JsValue`.
+#[derive(Debug)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(msg.to_string())
+    }
+}
+
+impl From<Error> for JsValue {
+    fn from(error: Error) -> JsValue {
+        JsValue::from_str(&error.0)
+    }
+}
+
+/// Transfers ownership of `value`'s slot in the JS heap to an intrinsic.
+fn into_idx(value: JsValue) -> u32 {
+    let idx = value.idx;
+    mem::forget(value);
+    idx
+}
+
+fn is_map(value: &JsValue) -> bool {
+    unsafe { __wbindgen_is_map(value.idx) == 1 }
+}
+
+fn is_uint8_array(value: &JsValue) -> bool {
+    unsafe { __wbindgen_is_uint8_array(value.idx) == 1 }
+}
+
+fn array_new() -> JsValue {
+    unsafe { JsValue::_new(crate::__wbindgen_array_new()) }
+}
+
+fn array_push(array: &JsValue, value: JsValue) {
+    unsafe { crate::__wbindgen_array_push(array.idx, into_idx(value)) }
+}
+
+fn array_get(array: &JsValue, index: u32) -> JsValue {
+    unsafe { JsValue::_new(crate::__wbindgen_array_get(array.idx, index)) }
+}
+
+/// Returns the `length` property of an array or typed array.
+fn length(value: &JsValue) -> u32 {
+    unsafe { crate::__wbindgen_length(value.idx) }
+}
+
+fn object_new() -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_object_new()) }
+}
+
+fn object_set(object: &JsValue, key: JsValue, value: JsValue) {
+    unsafe { __wbindgen_object_set(object.idx, into_idx(key), into_idx(value)) }
+}
+
+fn map_new() -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_map_new()) }
+}
+
+fn map_set(map: &JsValue, key: JsValue, value: JsValue) {
+    unsafe { __wbindgen_map_set(map.idx, into_idx(key), into_idx(value)) }
+}
+
+/// Returns an array of the `[key, value]` pairs of a `Map` or of the own
+/// enumerable properties of an object.
+fn entries(value: &JsValue) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_entries(value.idx)) }
+}
+
+fn uint8_array_new(bytes: &[u8]) -> JsValue {
+    unsafe { JsValue::_new(__wbindgen_uint8_array_new(bytes.as_ptr(), bytes.len())) }
+}
+
+fn uint8_array_to_vec(array: &JsValue) -> Vec<u8> {
+    let mut bytes = vec![0; length(array) as usize];
+    unsafe {
+        __wbindgen_uint8_array_copy_to(array.idx, bytes.as_mut_ptr(), bytes.len());
+    }
+    bytes
+}
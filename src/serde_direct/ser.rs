@@ -0,0 +1,459 @@
+use std::prelude::v1::*;
+
+use serde::ser::{self, Serialize};
+
+use super::{Error, MAX_SAFE_INTEGER};
+use crate::JsValue;
+
+/// A Serde serializer which converts Rust values into `JsValue`s.
+///
+/// See the [module documentation](index.html) for how values are mapped by
+/// default. `JsValue::from_serde_direct` uses a serializer with the default
+/// configuration, other configurations can be used by serializing into a
+/// reference to a `Serializer`:
+///
+/// ```rust,no_run
+/// use serde::Serialize;
+/// use wasm_bindgen::serde_direct::Serializer;
+///
+/// let serializer = Serializer::new().serialize_missing_as_null(true);
+/// let value = Some(vec![None, Some(1)]).serialize(&serializer).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Serializer {
+    maps_as_objects: bool,
+    bytes_as_arrays: bool,
+    missing_as_null: bool,
+    large_number_types_as_bigints: bool,
+}
+
+impl Serializer {
+    /// Creates a serializer with the default configuration.
+    pub fn new() -> Serializer {
+        Serializer::default()
+    }
+
+    /// Whether maps are serialized as plain objects rather than as `Map`s.
+    ///
+    /// Keys of maps are converted to strings when they're used as property
+    /// names. Defaults to `false`.
+    pub fn serialize_maps_as_objects(mut self, value: bool) -> Serializer {
+        self.maps_as_objects = value;
+        self
+    }
+
+    /// Whether byte buffers are serialized as arrays of numbers rather than as
+    /// `Uint8Array`s. Defaults to `false`.
+    pub fn serialize_bytes_as_arrays(mut self, value: bool) -> Serializer {
+        self.bytes_as_arrays = value;
+        self
+    }
+
+    /// Whether `None`, `()` and unit structs are serialized as `null` rather
+    /// than `undefined`, as they are when going through JSON. Defaults to
+    /// `false`.
+    pub fn serialize_missing_as_null(mut self, value: bool) -> Serializer {
+        self.missing_as_null = value;
+        self
+    }
+
+    /// Whether all 64 and 128-bit integers are serialized as `BigInt`s, rather
+    /// than only those which can't be represented exactly as JS numbers.
+    /// Defaults to `false`.
+    pub fn serialize_large_number_types_as_bigints(mut self, value: bool) -> Serializer {
+        self.large_number_types_as_bigints = value;
+        self
+    }
+
+    fn missing(&self) -> JsValue {
+        if self.missing_as_null {
+            JsValue::NULL
+        } else {
+            JsValue::UNDEFINED
+        }
+    }
+
+    /// Serializes a 64 or 128-bit integer, where `n` is `value` converted to a
+    /// (possibly inexact) `f64`.
+    fn large_number<T: Into<JsValue>>(&self, value: T, n: f64) -> JsValue {
+        if !self.large_number_types_as_bigints && n.abs() <= MAX_SAFE_INTEGER {
+            JsValue::from_f64(n)
+        } else {
+            value.into()
+        }
+    }
+}
+
+/// Wraps `value` in an object with a single property named after `variant`,
+/// which is how enum variants with data are represented.
+fn variant(variant: &'static str, value: JsValue) -> JsValue {
+    let object = super::object_new();
+    super::object_set(&object, JsValue::from_str(variant), value);
+    object
+}
+
+impl<'a> ser::Serializer for &'a Serializer {
+    type Ok = JsValue;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'a>;
+    type SerializeTuple = ArraySerializer<'a>;
+    type SerializeTupleStruct = ArraySerializer<'a>;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer<'a>>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = ObjectSerializer<'a>;
+    type SerializeStructVariant = VariantSerializer<ObjectSerializer<'a>>;
+
+    fn serialize_bool(self, v: bool) -> Result<JsValue, Error> {
+        Ok(JsValue::from_bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JsValue, Error> {
+        Ok(self.large_number(v, v as f64))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<JsValue, Error> {
+        Ok(self.large_number(v, v as f64))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JsValue, Error> {
+        Ok(self.large_number(v, v as f64))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<JsValue, Error> {
+        Ok(self.large_number(v, v as f64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JsValue, Error> {
+        Ok(JsValue::from_f64(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<JsValue, Error> {
+        Ok(JsValue::from_str(v.encode_utf8(&mut [0; 4])))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JsValue, Error> {
+        Ok(JsValue::from_str(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsValue, Error> {
+        if !self.bytes_as_arrays {
+            return Ok(super::uint8_array_new(v));
+        }
+        let array = super::array_new();
+        for byte in v {
+            super::array_push(&array, JsValue::from_f64((*byte).into()));
+        }
+        Ok(array)
+    }
+
+    fn serialize_none(self) -> Result<JsValue, Error> {
+        Ok(self.missing())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<JsValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JsValue, Error> {
+        Ok(self.missing())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsValue, Error> {
+        Ok(self.missing())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JsValue, Error> {
+        Ok(JsValue::from_str(variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<JsValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant_name: &'static str,
+        value: &T,
+    ) -> Result<JsValue, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(variant(variant_name, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ArraySerializer<'a>, Error> {
+        Ok(ArraySerializer {
+            ser: self,
+            array: super::array_new(),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ArraySerializer<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<ArraySerializer<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<ArraySerializer<'a>>, Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer<'a>, Error> {
+        let map = if self.maps_as_objects {
+            super::object_new()
+        } else {
+            super::map_new()
+        };
+        Ok(MapSerializer {
+            ser: self,
+            map,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<ObjectSerializer<'a>, Error> {
+        Ok(ObjectSerializer {
+            ser: self,
+            object: super::object_new(),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<ObjectSerializer<'a>>, Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_struct(name, len)?,
+        })
+    }
+}
+
+/// Serializes sequences, tuples and tuple structs into arrays.
+#[doc(hidden)]
+pub struct ArraySerializer<'a> {
+    ser: &'a Serializer,
+    array: JsValue,
+}
+
+impl<'a> ser::SerializeSeq for ArraySerializer<'a> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        super::array_push(&self.array, value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        Ok(self.array)
+    }
+}
+
+impl<'a> ser::SerializeTuple for ArraySerializer<'a> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for ArraySerializer<'a> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializes maps into `Map`s, or into plain objects if configured to.
+#[doc(hidden)]
+pub struct MapSerializer<'a> {
+    ser: &'a Serializer,
+    map: JsValue,
+    key: Option<JsValue>,
+}
+
+impl<'a> ser::SerializeMap for MapSerializer<'a> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(self.ser)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Err(ser::Error::custom("map value serialized before its key")),
+        };
+        let value = value.serialize(self.ser)?;
+        if self.ser.maps_as_objects {
+            super::object_set(&self.map, key, value);
+        } else {
+            super::map_set(&self.map, key, value);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        Ok(self.map)
+    }
+}
+
+/// Serializes structs into plain objects.
+#[doc(hidden)]
+pub struct ObjectSerializer<'a> {
+    ser: &'a Serializer,
+    object: JsValue,
+}
+
+impl<'a> ser::SerializeStruct for ObjectSerializer<'a> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(self.ser)?;
+        super::object_set(&self.object, JsValue::from_str(key), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        Ok(self.object)
+    }
+}
+
+/// Serializes tuple and struct variants of enums into an object with a single
+/// property named after the variant.
+#[doc(hidden)]
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<'a> ser::SerializeTupleVariant for VariantSerializer<ArraySerializer<'a>> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        let array = ser::SerializeSeq::end(self.inner)?;
+        Ok(variant(self.variant, array))
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for VariantSerializer<ObjectSerializer<'a>> {
+    type Ok = JsValue;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<JsValue, Error> {
+        let object = ser::SerializeStruct::end(self.inner)?;
+        Ok(variant(self.variant, object))
+    }
+}
@@ -0,0 +1,276 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::JsValue;
+
+/// A trait for fallibly converting an owned `JsValue` into a Rust value.
+///
+/// Unlike `JsValue::as_f64` and friends, the conversions implemented here
+/// check both the type and the range of the JS value, so for example
+/// converting `300` to a `u8`, `1.5` to a `u32` or `"1"` to a `u32` all fail
+/// rather than silently truncating or coercing the value.
+///
+/// This trait is implemented for:
+///
+/// * All integer types. 8, 16 and 32-bit integers (including `isize` and
+///   `usize`) are converted from JS numbers which are integers in range,
+///   while 64 and 128-bit integers are converted from `BigInt`s in range as
+///   well as from numbers which are safe integers in range, that is, integers
+///   no larger in magnitude than `Number.MAX_SAFE_INTEGER`.
+/// * `f32` and `f64`, converted from JS numbers. `f32` additionally rejects
+///   finite numbers too large to represent.
+/// * `bool`, `char`, and `String`, converted from JS booleans, strings with a
+///   single character, and strings respectively.
+/// * `Vec<T>`, converted from arrays and typed arrays whose elements can all
+///   be converted to `T`.
+/// * `Option<T>`, where `null` and `undefined` convert to `None`.
+/// * `JsValue` itself, which always succeeds.
+/// * Structs exported with `#[wasm_bindgen]`, converted from instances of the
+///   corresponding JS class. On success the JS object no longer owns the Rust
+///   value, just as if it had been passed by value to an exported function.
+///
+/// Everything except `Option<T>` also implements `TryFrom<JsValue>` with
+/// `TryFromJsValueError` as the error type.
+pub trait TryFromJsValue: Sized {
+    /// Performs the conversion, returning an error describing what was
+    /// expected if `value` doesn't have the right type or is out of range.
+    fn try_from_js_value(value: JsValue) -> Result<Self, TryFromJsValueError>;
+}
+
+/// The error returned when a `JsValue` can't be converted with
+/// `TryFromJsValue`.
+///
+/// The error hands back the value which failed to convert, which for
+/// collections is the first element which failed to convert.
+#[derive(Debug)]
+pub struct TryFromJsValueError {
+    value: JsValue,
+    expected: &'static str,
+}
+
+impl TryFromJsValueError {
+    /// Creates an error for `value`, where `expected` describes the kind of
+    /// value which was expected, such as "a string".
+    pub fn new(value: JsValue, expected: &'static str) -> TryFromJsValueError {
+        TryFromJsValueError { value, expected }
+    }
+
+    /// Returns a description of the kind of value which was expected.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Returns the value which failed to convert.
+    pub fn value(&self) -> &JsValue {
+        &self.value
+    }
+
+    /// Returns the value which failed to convert, consuming the error.
+    pub fn into_value(self) -> JsValue {
+        self.value
+    }
+}
+
+impl fmt::Display for TryFromJsValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {}, found {:?}", self.expected, self.value)
+    }
+}
+
+externs! {
+    #[link(wasm_import_module = "__wbindgen_placeholder__")]
+    extern "C" {
+        fn __wbindgen_is_typed_array(idx: u32) -> u32;
+    }
+}
+
+macro_rules! try_from {
+    ($($t:ty)*) => ($(
+        impl TryFrom<JsValue> for $t {
+            type Error = TryFromJsValueError;
+
+            #[inline]
+            fn try_from(value: JsValue) -> Result<$t, TryFromJsValueError> {
+                <$t as TryFromJsValue>::try_from_js_value(value)
+            }
+        }
+    )*)
+}
+
+macro_rules! integers {
+    ($($t:ident)*) => ($(
+        impl TryFromJsValue for $t {
+            fn try_from_js_value(value: JsValue) -> Result<$t, TryFromJsValueError> {
+                match value.as_f64() {
+                    Some(n)
+                        if n >= $t::min_value() as f64
+                            && n <= $t::max_value() as f64
+                            && n as $t as f64 == n =>
+                    {
+                        Ok(n as $t)
+                    }
+                    _ => Err(TryFromJsValueError::new(
+                        value,
+                        concat!("an integer in the range of `", stringify!($t), "`"),
+                    )),
+                }
+            }
+        }
+
+        try_from!($t);
+    )*)
+}
+
+integers! { i8 u8 i16 u16 i32 u32 isize usize }
+
+/// The largest integer such that it and all smaller integers can be exactly
+/// represented by a JS number, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Returns the value of `value` if it's a JS number which is a safe integer,
+/// that is, an integer which JS represents exactly.
+fn as_safe_integer(value: &JsValue) -> Option<i64> {
+    match value.as_f64() {
+        Some(n) if n >= -MAX_SAFE_INTEGER && n <= MAX_SAFE_INTEGER && n as i64 as f64 == n => {
+            Some(n as i64)
+        }
+        _ => None,
+    }
+}
+
+macro_rules! big_integers {
+    ($($t:ident $as_big:ident)*) => ($(
+        impl TryFromJsValue for $t {
+            fn try_from_js_value(value: JsValue) -> Result<$t, TryFromJsValueError> {
+                let n = match value.$as_big() {
+                    Some(n) => $t::try_from(n).ok(),
+                    None => as_safe_integer(&value).and_then(|n| $t::try_from(n).ok()),
+                };
+                match n {
+                    Some(n) => Ok(n),
+                    None => Err(TryFromJsValueError::new(
+                        value,
+                        concat!(
+                            "a `BigInt` or safe integer in the range of `",
+                            stringify!($t),
+                            "`",
+                        ),
+                    )),
+                }
+            }
+        }
+
+        try_from!($t);
+    )*)
+}
+
+big_integers! { i64 as_i128 u64 as_u128 i128 as_i128 u128 as_u128 }
+
+impl TryFromJsValue for f32 {
+    fn try_from_js_value(value: JsValue) -> Result<f32, TryFromJsValueError> {
+        match value.as_f64() {
+            Some(n) if !n.is_finite() || (n >= f32::MIN as f64 && n <= f32::MAX as f64) => {
+                Ok(n as f32)
+            }
+            _ => Err(TryFromJsValueError::new(
+                value,
+                "a number in the range of `f32`",
+            )),
+        }
+    }
+}
+
+impl TryFromJsValue for f64 {
+    fn try_from_js_value(value: JsValue) -> Result<f64, TryFromJsValueError> {
+        match value.as_f64() {
+            Some(n) => Ok(n),
+            None => Err(TryFromJsValueError::new(value, "a number")),
+        }
+    }
+}
+
+impl TryFromJsValue for bool {
+    fn try_from_js_value(value: JsValue) -> Result<bool, TryFromJsValueError> {
+        match value.as_bool() {
+            Some(b) => Ok(b),
+            None => Err(TryFromJsValueError::new(value, "a boolean")),
+        }
+    }
+}
+
+try_from! { f32 f64 bool }
+
+impl TryFromJsValue for JsValue {
+    #[inline]
+    fn try_from_js_value(value: JsValue) -> Result<JsValue, TryFromJsValueError> {
+        Ok(value)
+    }
+}
+
+impl<T: TryFromJsValue> TryFromJsValue for Option<T> {
+    fn try_from_js_value(value: JsValue) -> Result<Option<T>, TryFromJsValueError> {
+        if value.is_null() || value.is_undefined() {
+            Ok(None)
+        } else {
+            T::try_from_js_value(value).map(Some)
+        }
+    }
+}
+
+if_std! {
+    use std::prelude::v1::*;
+
+    impl std::error::Error for TryFromJsValueError {}
+
+    impl From<TryFromJsValueError> for JsValue {
+        fn from(error: TryFromJsValueError) -> JsValue {
+            JsValue::from_str(&error.to_string())
+        }
+    }
+
+    impl TryFromJsValue for char {
+        fn try_from_js_value(value: JsValue) -> Result<char, TryFromJsValueError> {
+            if let Some(s) = value.as_string() {
+                let mut chars = s.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    return Ok(c);
+                }
+            }
+            Err(TryFromJsValueError::new(value, "a string with a single character"))
+        }
+    }
+
+    impl TryFromJsValue for String {
+        fn try_from_js_value(value: JsValue) -> Result<String, TryFromJsValueError> {
+            match value.as_string() {
+                Some(s) => Ok(s),
+                None => Err(TryFromJsValueError::new(value, "a string")),
+            }
+        }
+    }
+
+    try_from! { char String }
+
+    impl<T: TryFromJsValue> TryFromJsValue for Vec<T> {
+        fn try_from_js_value(value: JsValue) -> Result<Vec<T>, TryFromJsValueError> {
+            let is_typed_array = unsafe { __wbindgen_is_typed_array(value.idx) == 1 };
+            if !value.is_array() && !is_typed_array {
+                return Err(TryFromJsValueError::new(value, "an array or typed array"));
+            }
+            let len = unsafe { crate::__wbindgen_length(value.idx) };
+            (0..len)
+                .map(|i| unsafe { JsValue::_new(crate::__wbindgen_array_get(value.idx, i)) })
+                .map(T::try_from_js_value)
+                .collect()
+        }
+    }
+
+    impl<T: TryFromJsValue> TryFrom<JsValue> for Vec<T> {
+        type Error = TryFromJsValueError;
+
+        #[inline]
+        fn try_from(value: JsValue) -> Result<Vec<T>, TryFromJsValueError> {
+            <Vec<T> as TryFromJsValue>::try_from_js_value(value)
+        }
+    }
+}
@@ -61,3 +61,12 @@ exports.assert_function_table = (x, i) => {
   assert.ok(x instanceof WebAssembly.Table);
   assert.strictEqual(x.get(i), rawWasm.function_table_lookup);
 };
+
+exports.object_value = () => ({a: 1});
+exports.array_value = () => [1, 2];
+exports.bigint_value = n => BigInt(n);
+exports.function_value = () => () => null;
+exports.error_value = () => new TypeError('a');
+exports.error_ctor = () => Error;
+exports.type_error_ctor = () => TypeError;
+exports.range_error_ctor = () => RangeError;
@@ -10,6 +10,14 @@ extern "C" {
     fn assert_null(v: JsValue);
     fn debug_values() -> JsValue;
     fn assert_function_table(a: JsValue, b: usize);
+    fn object_value() -> JsValue;
+    fn array_value() -> JsValue;
+    fn bigint_value(n: i32) -> JsValue;
+    fn function_value() -> JsValue;
+    fn error_value() -> JsValue;
+    fn error_ctor() -> JsValue;
+    fn type_error_ctor() -> JsValue;
+    fn range_error_ctor() -> JsValue;
 }
 
 #[wasm_bindgen_test]
@@ -126,6 +134,112 @@ pub fn eq_test1(a: &JsValue) -> bool {
     a == a
 }
 
+#[wasm_bindgen_test]
+fn typeof_works() {
+    assert_eq!(JsValue::from(1).js_typeof(), "number");
+    assert_eq!(JsValue::from("a").js_typeof(), "string");
+    assert_eq!(JsValue::TRUE.js_typeof(), "boolean");
+    assert_eq!(JsValue::UNDEFINED.js_typeof(), "undefined");
+    assert_eq!(JsValue::NULL.js_typeof(), "object");
+    assert_eq!(JsValue::symbol(None).js_typeof(), "symbol");
+    assert_eq!(object_value().js_typeof(), "object");
+    assert_eq!(array_value().js_typeof(), "object");
+    assert_eq!(bigint_value(1).js_typeof(), "bigint");
+    assert_eq!(function_value().js_typeof(), "function");
+
+    assert!(array_value().is_array());
+    assert!(!object_value().is_array());
+    assert!(!JsValue::from("a").is_array());
+    assert!(bigint_value(1).is_bigint());
+    assert!(!JsValue::from(1).is_bigint());
+}
+
+#[wasm_bindgen_test]
+fn loose_eq_works() {
+    assert!(JsValue::from(1).loose_eq(&JsValue::from("1")));
+    assert!(JsValue::NULL.loose_eq(&JsValue::UNDEFINED));
+    assert!(JsValue::from(1).loose_eq(&bigint_value(1)));
+    assert!(!JsValue::from(1).loose_eq(&JsValue::from(2)));
+    assert!(JsValue::from(1) != JsValue::from("1"));
+}
+
+#[wasm_bindgen_test]
+fn in_works() {
+    assert!(JsValue::from("a").js_in(&object_value()));
+    assert!(!JsValue::from("b").js_in(&object_value()));
+    assert!(JsValue::from("toString").js_in(&object_value()));
+    assert!(JsValue::from("length").js_in(&array_value()));
+    assert!(JsValue::from(1).js_in(&array_value()));
+    assert!(!JsValue::from(2).js_in(&array_value()));
+}
+
+#[wasm_bindgen_test]
+fn instanceof_works() {
+    assert!(error_value().js_instanceof(&type_error_ctor()));
+    assert!(error_value().js_instanceof(&error_ctor()));
+    assert!(!error_value().js_instanceof(&range_error_ctor()));
+    assert!(!object_value().js_instanceof(&error_ctor()));
+    assert!(!JsValue::from(1).js_instanceof(&error_ctor()));
+}
+
+#[wasm_bindgen_test]
+fn falsy_works() {
+    let falsy = [
+        JsValue::FALSE,
+        JsValue::from(0),
+        JsValue::from(std::f64::NAN),
+        JsValue::from(""),
+        JsValue::NULL,
+        JsValue::UNDEFINED,
+        bigint_value(0),
+    ];
+    for value in falsy.iter() {
+        assert!(value.is_falsy(), "{:?} should be falsy", value);
+        assert!(!value.is_truthy(), "{:?} shouldn't be truthy", value);
+    }
+
+    let truthy = [
+        JsValue::TRUE,
+        JsValue::from(-1),
+        JsValue::from("0"),
+        object_value(),
+        array_value(),
+        function_value(),
+        bigint_value(1),
+    ];
+    for value in truthy.iter() {
+        assert!(value.is_truthy(), "{:?} should be truthy", value);
+        assert!(!value.is_falsy(), "{:?} shouldn't be falsy", value);
+    }
+}
+
+#[wasm_bindgen_test]
+fn operators_work() {
+    let a = JsValue::from(6);
+    let b = JsValue::from(4);
+    assert_eq!(&a + &b, 10);
+    assert_eq!(&a - &b, 2);
+    assert_eq!(&a * &b, 24);
+    assert_eq!(&a / &b, 1.5);
+    assert_eq!(&a % &b, 2);
+    assert_eq!(a.pow(&b), 1296);
+    assert_eq!(&a & &b, 4);
+    assert_eq!(&a | &b, 6);
+    assert_eq!(&a ^ &b, 2);
+    assert_eq!(&a << &b, 96);
+    assert_eq!(JsValue::from(-16) >> &b, -1);
+
+    // Operators behave as they do in JS with values of other types.
+    assert_eq!(JsValue::from("a") + &b, "a4");
+    assert_eq!(&a + JsValue::from("a"), "6a");
+    assert_eq!(JsValue::from("6") * JsValue::from("4"), 24);
+    assert!((&a / JsValue::from(0)).as_f64().unwrap().is_infinite());
+
+    assert_eq!(bigint_value(6) * bigint_value(4), bigint_value(24));
+    assert_eq!(bigint_value(7) / bigint_value(2), bigint_value(3));
+    assert_eq!(bigint_value(2).pow(&bigint_value(64)).js_typeof(), "bigint");
+}
+
 #[wasm_bindgen_test]
 fn null_keeps_working() {
     assert_null(JsValue::null());
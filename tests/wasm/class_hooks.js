@@ -0,0 +1,57 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+const util = require('util');
+
+exports.js_iterator_hooks = () => {
+    const range = new wasm.HooksRange(1, 4);
+    assert.deepStrictEqual([...range], [1, 2, 3]);
+    assert.deepStrictEqual(Array.from(range), [1, 2, 3]);
+
+    const seen = [];
+    for (const i of range) {
+        seen.push(i);
+        if (i === 2)
+            break;
+    }
+    assert.deepStrictEqual(seen, [1, 2]);
+
+    const iter = range[Symbol.iterator]();
+    assert.strictEqual(iter[Symbol.iterator](), iter);
+    assert.deepStrictEqual(iter.return(5), { value: 5, done: true });
+    assert.deepStrictEqual(iter.next(), { value: undefined, done: true });
+
+    range.free();
+};
+
+exports.js_async_iterator_hook = async () => {
+    const range = new wasm.HooksRange(1, 4);
+
+    const seen = [];
+    for await (const item of range)
+        seen.push(item);
+    assert.deepStrictEqual(seen, ['item 1', 'item 2', 'item 3']);
+
+    const iter = range[Symbol.asyncIterator]();
+    const next = iter.next();
+    assert.ok(next instanceof Promise);
+    assert.deepStrictEqual(await next, { value: 'item 1', done: false });
+    assert.deepStrictEqual(await iter.return(5), { value: 5, done: true });
+    assert.deepStrictEqual(await iter.next(), { value: undefined, done: true });
+
+    range.free();
+};
+
+exports.js_conversion_hooks = () => {
+    const range = new wasm.HooksRange(1, 4);
+    assert.strictEqual(String(range), '1..4');
+    assert.strictEqual(`${range}`, '1..4');
+    assert.strictEqual(util.inspect(range), 'HooksRange(1..4)');
+    range.free();
+
+    const num = new wasm.HooksNumber(1.5);
+    assert.strictEqual(num.valueOf(), 1.5);
+    assert.strictEqual(num + 1, 2.5);
+    assert.strictEqual(num * 2, 3);
+    assert.strictEqual(`${num}`, '<1.5>');
+    num.free();
+};
@@ -0,0 +1,89 @@
+use futures::{stream, Future};
+use wasm_bindgen::iter::into_js_iterator;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{stream_to_async_iterator, JsFuture};
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/class_hooks.js")]
+extern "C" {
+    fn js_iterator_hooks();
+    fn js_async_iterator_hook() -> js_sys::Promise;
+    fn js_conversion_hooks();
+}
+
+#[wasm_bindgen]
+pub struct HooksRange {
+    start: u32,
+    end: u32,
+}
+
+#[wasm_bindgen]
+impl HooksRange {
+    #[wasm_bindgen(constructor)]
+    pub fn new(start: u32, end: u32) -> HooksRange {
+        HooksRange { start, end }
+    }
+
+    #[wasm_bindgen(iterator)]
+    pub fn iter(&self) -> JsValue {
+        into_js_iterator(self.start..self.end)
+    }
+
+    #[wasm_bindgen(async_iterator)]
+    pub fn async_iter(&self) -> JsValue {
+        let items = (self.start..self.end).map(|i| format!("item {}", i));
+        stream_to_async_iterator(stream::iter_ok(items))
+    }
+
+    #[wasm_bindgen(to_string)]
+    pub fn display(&self) -> String {
+        format!("{}..{}", self.start, self.end)
+    }
+
+    #[wasm_bindgen(inspect)]
+    pub fn debug(&self) -> String {
+        format!("HooksRange({}..{})", self.start, self.end)
+    }
+}
+
+#[wasm_bindgen]
+pub struct HooksNumber {
+    value: f64,
+}
+
+#[wasm_bindgen]
+impl HooksNumber {
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: f64) -> HooksNumber {
+        HooksNumber { value }
+    }
+
+    #[wasm_bindgen(value_of)]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    #[wasm_bindgen(to_primitive)]
+    pub fn to_primitive(&self, hint: String) -> JsValue {
+        if hint == "string" {
+            format!("<{}>", self.value).into()
+        } else {
+            self.value.into()
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn iterator_hooks() {
+    js_iterator_hooks();
+}
+
+#[wasm_bindgen_test(async)]
+fn async_iterator_hook() -> impl Future<Item = (), Error = JsValue> {
+    JsFuture::from(js_async_iterator_hook()).map(|_| ())
+}
+
+#[wasm_bindgen_test]
+fn conversion_hooks() {
+    js_conversion_hooks();
+}
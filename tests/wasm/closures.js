@@ -117,6 +117,15 @@ exports.calling_it_throws = a => {
 
 exports.call_val = f => f();
 
+// Finalization callbacks don't run during the collection itself but are
+// queued as a task of their own, so wait for that task too.
+exports.collect_garbage = () => new Promise(resolve => {
+  setTimeout(() => {
+    gc();
+    setTimeout(resolve, 0);
+  }, 0);
+});
+
 exports.pass_reference_first_arg_twice = (a, b, c) => {
   b(a);
   c(a);
@@ -124,7 +133,9 @@ exports.pass_reference_first_arg_twice = (a, b, c) => {
 };
 
 exports.call_destroyed = f => {
-  assert.throws(f, /invoked recursively or destroyed/);
+  assert.throws(f, err => {
+    return err.name === 'DroppedClosureError' && /invoked after being dropped/.test(err.message);
+  });
 };
 
 let FORGOTTEN_CLOSURE = null;
@@ -136,3 +147,39 @@ exports.js_store_forgotten_closure = f => {
 exports.js_call_forgotten_closure = () => {
   FORGOTTEN_CLOSURE();
 };
+
+exports.pass_str_and_bytes = f => {
+  f('foo', new Uint8Array([1, 2, 3]));
+};
+
+exports.pass_value_and_str = f => {
+  f(1, 'foo');
+};
+
+exports.pass_u32_and_bytes = f => f(3, new Uint8Array([1, 2, 3]));
+
+exports.pass_mixed = (a, f) => {
+  const ret = f(2, a, 'bar', 4);
+  a.free();
+  return ret;
+};
+
+exports.result_ok_and_throws = f => {
+  assert.strictEqual(f('12'), 12);
+  assert.throws(() => f('x'), /not a number: x/);
+};
+
+exports.call_dropped_with_policies = (ignored, logged, thrown) => {
+  assert.strictEqual(ignored(), undefined);
+  assert.strictEqual(logged(), undefined);
+  assert.throws(() => thrown('a', 1), err => {
+    return err.name === 'DroppedClosureError' &&
+      err.message.includes('`dyn FnMut(&str, u32) -> String`');
+  });
+};
+
+let DROPS = 0;
+exports.count_drops = f => {
+  f.ondrop = () => DROPS += 1;
+};
+exports.drops = () => DROPS;
@@ -1,8 +1,11 @@
 #![cfg(feature = "nightly")]
 
+use futures::Future;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use wasm_bindgen::closure::DropPolicy;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::*;
 
 #[wasm_bindgen(module = "tests/wasm/closures.js")]
@@ -96,6 +99,8 @@ extern "C" {
     #[wasm_bindgen(js_name = calling_it_throws)]
     fn call_val_throws(f: &JsValue) -> bool;
 
+    fn collect_garbage() -> js_sys::Promise;
+
     fn pass_reference_first_arg_twice(
         a: RefFirstArgument,
         b: &Closure<FnMut(&RefFirstArgument)>,
@@ -111,6 +116,33 @@ extern "C" {
 
     fn js_store_forgotten_closure(closure: &Closure<Fn()>);
     fn js_call_forgotten_closure();
+
+    fn call_dropped_with_policies(ignored: &JsValue, logged: &JsValue, thrown: &JsValue);
+    fn count_drops(f: &JsValue);
+    fn drops() -> u32;
+
+    #[wasm_bindgen(js_name = pass_str_and_bytes)]
+    fn pass_str_and_bytes_closure(a: &Closure<FnMut(&str, &[u8])>);
+    #[wasm_bindgen(js_name = pass_str_and_bytes)]
+    fn pass_str_and_bytes_stack(a: &Fn(&str, &[u8]));
+    fn pass_value_and_str(a: &Closure<FnMut(JsValue, &str)>);
+    #[wasm_bindgen(js_name = pass_u32_and_bytes)]
+    fn pass_u32_and_bytes_closure(a: &Closure<Fn(u32, &[u8]) -> u32>) -> u32;
+    #[wasm_bindgen(js_name = pass_u32_and_bytes)]
+    fn pass_u32_and_bytes_stack(a: &Fn(u32, &[u8]) -> u32) -> u32;
+    #[wasm_bindgen(js_name = pass_mixed)]
+    fn pass_mixed_closure(
+        a: RefFirstArgument,
+        b: &Closure<Fn(u32, &RefFirstArgument, String, &JsValue) -> u32>,
+    ) -> u32;
+    #[wasm_bindgen(js_name = pass_mixed)]
+    fn pass_mixed_stack(
+        a: RefFirstArgument,
+        b: &mut FnMut(u32, &RefFirstArgument, String, &JsValue) -> u32,
+    ) -> u32;
+    fn result_ok_and_throws(a: &Closure<Fn(&str) -> Result<u32, JsValue>>);
+    #[wasm_bindgen(js_name = result_ok_and_throws)]
+    fn result_ok_and_throws_stack(a: &Fn(&str) -> Result<u32, JsValue>);
 }
 
 #[wasm_bindgen_test]
@@ -287,6 +319,50 @@ fn once_into_js() {
     assert!(call_val_throws(&f));
 }
 
+#[wasm_bindgen_test]
+fn into_js_value() {
+    let dropped = Rc::new(Cell::new(false));
+    let dropper = Dropper(dropped.clone());
+    let calls = Rc::new(Cell::new(0));
+
+    let f = Closure::wrap(Box::new({
+        let calls = calls.clone();
+        move || {
+            drop(&dropper);
+            calls.set(calls.get() + 1);
+        }
+    }) as Box<FnMut()>)
+    .into_js_value();
+
+    // The Rust closure is now owned by the JS function, so it's still alive
+    // and can be called any number of times.
+    call_val(&f);
+    call_val(&f);
+    assert_eq!(calls.get(), 2);
+    assert!(!dropped.get());
+}
+
+// Only run on CI, as this needs Node.js started with `--expose-gc` and the
+// bindings generated with `WASM_BINDGEN_WEAKREF` set.
+#[wasm_bindgen_test(async)]
+#[ignore = "needs `--expose-gc` and weak references"]
+fn into_js_value_dropped_after_gc() -> impl Future<Item = (), Error = JsValue> {
+    let dropped = Rc::new(Cell::new(false));
+    let dropper = Dropper(dropped.clone());
+
+    let f = Closure::wrap(Box::new(move || drop(&dropper)) as Box<FnMut()>).into_js_value();
+    call_val(&f);
+    count_drops(&f);
+    let drops_before = drops();
+    drop(f);
+    assert!(!dropped.get());
+
+    JsFuture::from(collect_garbage()).map(move |_| {
+        assert!(dropped.get());
+        assert_eq!(drops(), drops_before + 1);
+    })
+}
+
 #[wasm_bindgen_test]
 fn long_lived_dropping() {
     let hit = Rc::new(Cell::new(false));
@@ -584,3 +660,143 @@ fn forget_works() {
     a.forget();
     js_call_forgotten_closure();
 }
+
+#[wasm_bindgen_test]
+fn borrowed_str_and_slice_arguments() {
+    let calls = Rc::new(Cell::new(0));
+    let a = {
+        let calls = calls.clone();
+        Closure::wrap(Box::new(move |s: &str, b: &[u8]| {
+            assert_eq!(s, "foo");
+            assert_eq!(b, [1, 2, 3]);
+            calls.set(calls.get() + 1);
+        }) as Box<FnMut(&str, &[u8])>)
+    };
+    pass_str_and_bytes_closure(&a);
+    assert_eq!(calls.get(), 1);
+
+    pass_str_and_bytes_stack(&|s, b| {
+        assert_eq!(s, "foo");
+        assert_eq!(b, [1, 2, 3]);
+        calls.set(calls.get() + 1);
+    });
+    assert_eq!(calls.get(), 2);
+
+    let once = {
+        let calls = calls.clone();
+        Closure::once(move |s: &str, b: &[u8]| {
+            assert_eq!(s, "foo");
+            assert_eq!(b, [1, 2, 3]);
+            calls.set(calls.get() + 1);
+        })
+    };
+    pass_str_and_bytes_closure(&once);
+    assert_eq!(calls.get(), 3);
+}
+
+#[wasm_bindgen_test]
+fn borrowed_arguments_after_owned_ones() {
+    let calls = Rc::new(Cell::new(0));
+    let a = {
+        let calls = calls.clone();
+        Closure::wrap(Box::new(move |v: JsValue, s: &str| {
+            assert_eq!(v.as_f64(), Some(1.0));
+            assert_eq!(s, "foo");
+            calls.set(calls.get() + 1);
+        }) as Box<FnMut(JsValue, &str)>)
+    };
+    pass_value_and_str(&a);
+    assert_eq!(calls.get(), 1);
+
+    let once = {
+        let calls = calls.clone();
+        Closure::once(move |v: JsValue, s: &str| {
+            assert_eq!(v.as_f64(), Some(1.0));
+            assert_eq!(s, "foo");
+            calls.set(calls.get() + 1);
+        })
+    };
+    pass_value_and_str(&once);
+    assert_eq!(calls.get(), 2);
+
+    let sum = Closure::wrap(
+        Box::new(|n: u32, b: &[u8]| n + u32::from(b[2])) as Box<Fn(u32, &[u8]) -> u32>
+    );
+    assert_eq!(pass_u32_and_bytes_closure(&sum), 6);
+    assert_eq!(pass_u32_and_bytes_stack(&|n, b| n * b.len() as u32), 9);
+}
+
+#[wasm_bindgen_test]
+fn mixed_owned_and_borrowed_arguments() {
+    let a = Closure::wrap(
+        Box::new(|n: u32, x: &RefFirstArgument, s: String, v: &JsValue| {
+            assert_eq!(s, "bar");
+            assert_eq!(v.as_f64(), Some(4.0));
+            n + x.contents
+        }) as Box<Fn(u32, &RefFirstArgument, String, &JsValue) -> u32>,
+    );
+    assert_eq!(pass_mixed_closure(RefFirstArgument { contents: 3 }, &a), 5);
+
+    let mut calls = 0;
+    let n = pass_mixed_stack(RefFirstArgument { contents: 3 }, &mut |n, x, s, v| {
+        assert_eq!(s, "bar");
+        assert_eq!(v.as_f64(), Some(4.0));
+        calls += 1;
+        n * x.contents
+    });
+    assert_eq!(n, 6);
+    assert_eq!(calls, 1);
+}
+
+#[wasm_bindgen_test]
+fn result_return_throws() {
+    fn parse(s: &str) -> Result<u32, JsValue> {
+        s.parse()
+            .map_err(|_| JsValue::from(format!("not a number: {}", s)))
+    }
+
+    let a = Closure::wrap(Box::new(parse) as Box<Fn(&str) -> Result<u32, JsValue>>);
+    result_ok_and_throws(&a);
+    result_ok_and_throws_stack(&parse);
+}
+
+#[wasm_bindgen_test]
+fn drop_policy() {
+    let ignored = Closure::wrap(Box::new(|| {}) as Box<Fn()>).with_drop_policy(DropPolicy::Ignore);
+    let logged = Closure::wrap(Box::new(|| {}) as Box<FnMut()>).with_drop_policy(DropPolicy::Log);
+    let thrown =
+        Closure::wrap(Box::new(|_: &str, n: u32| n.to_string()) as Box<FnMut(&str, u32) -> String>)
+            .with_drop_policy(DropPolicy::Throw);
+
+    let (a, b, c) = (
+        ignored.as_ref().clone(),
+        logged.as_ref().clone(),
+        thrown.as_ref().clone(),
+    );
+    drop((ignored, logged, thrown));
+    call_dropped_with_policies(&a, &b, &c);
+}
+
+#[wasm_bindgen_test]
+fn is_alive_and_ondrop() {
+    let a = Closure::wrap(Box::new(|| {}) as Box<Fn()>);
+    let f = a.as_ref().clone();
+    count_drops(&f);
+    assert!(Closure::is_alive(&f));
+    assert_eq!(drops(), 0);
+    drop(a);
+    assert!(!Closure::is_alive(&f));
+    assert_eq!(drops(), 1);
+
+    // `FnOnce` functions are destroyed as they return.
+    let f = Closure::once_into_js(|| {});
+    count_drops(&f);
+    call_val(&f);
+    assert!(!Closure::is_alive(&f));
+    assert_eq!(drops(), 2);
+
+    let f = Closure::wrap(Box::new(|| {}) as Box<Fn()>).into_js_value();
+    assert!(Closure::is_alive(&f));
+    assert!(!Closure::is_alive(&JsValue::from(1)));
+    assert!(!Closure::is_alive(&js_sys::Function::new_no_args("")));
+}
@@ -0,0 +1,33 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_instantiated_classes = () => {
+    const a = new wasm.GenericsMatrixF32(2);
+    a.set(0, 0.1);
+    a.set(1, 0.2);
+    assert.strictEqual(a.sum(), Math.fround(Math.fround(0.1) + Math.fround(0.2)));
+
+    const b = new wasm.GenericsMatrixF64(2);
+    b.set(0, 0.1);
+    b.set(1, 0.2);
+    assert.strictEqual(b.sum(), 0.1 + 0.2);
+
+    const c = b.doubled();
+    assert.ok(c instanceof wasm.GenericsMatrixF64);
+    assert.ok(!(c instanceof wasm.GenericsMatrixF32));
+    assert.strictEqual(c.sum(), 0.2 + 0.4);
+
+    a.free();
+    b.free();
+    c.free();
+};
+
+exports.js_instantiated_fields = () => {
+    const a = new wasm.GenericsMatrixF64(3);
+    assert.strictEqual(a.rows, 3);
+    a.set(0, 1.5);
+    assert.strictEqual(a.first, 1.5);
+    a.first = 2.5;
+    assert.strictEqual(a.first, 2.5);
+    a.free();
+};
@@ -0,0 +1,56 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/generics.js")]
+extern "C" {
+    fn js_instantiated_classes();
+    fn js_instantiated_fields();
+}
+
+#[wasm_bindgen(instantiate(GenericsMatrixF32 = Matrix<f32>, GenericsMatrixF64 = Matrix<f64>))]
+pub struct Matrix<T> {
+    pub rows: u32,
+    pub first: T,
+    data: Vec<T>,
+}
+
+#[wasm_bindgen(instantiate(GenericsMatrixF32 = Matrix<f32>, GenericsMatrixF64 = Matrix<f64>))]
+impl<T: Copy + Default + std::ops::Add<Output = T>> Matrix<T> {
+    #[wasm_bindgen(constructor)]
+    pub fn new(rows: u32) -> Matrix<T> {
+        Matrix {
+            rows,
+            first: T::default(),
+            data: vec![T::default(); rows as usize],
+        }
+    }
+
+    pub fn set(&mut self, index: usize, value: T) {
+        self.data[index] = value;
+        if index == 0 {
+            self.first = value;
+        }
+    }
+
+    pub fn sum(&self) -> T {
+        self.data.iter().fold(T::default(), |a, b| a + *b)
+    }
+
+    pub fn doubled(&self) -> Self {
+        Matrix {
+            rows: self.rows,
+            first: self.first + self.first,
+            data: self.data.iter().map(|x| *x + *x).collect(),
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn instantiated_classes() {
+    js_instantiated_classes();
+}
+
+#[wasm_bindgen_test]
+fn instantiated_fields() {
+    js_instantiated_fields();
+}
@@ -0,0 +1,31 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+fn live_count() -> Option<u32> {
+    wasm_bindgen::heap_snapshot().map(|sites| sites.iter().map(|site| site.count).sum())
+}
+
+#[wasm_bindgen_test]
+fn snapshot_tracks_live_values() {
+    // Allocation sites are only recorded with `WASM_BINDGEN_HEAP_SNAPSHOT`
+    // set, and not with anyref.
+    let initial = match live_count() {
+        Some(count) => count,
+        None => return,
+    };
+
+    let vals: Vec<_> = (0..10).map(JsValue::from).collect();
+    let sites = wasm_bindgen::heap_snapshot().unwrap();
+    assert_eq!(
+        sites.iter().map(|site| site.count).sum::<u32>(),
+        initial + 10
+    );
+    assert!(sites.iter().any(|site| site.count >= 10));
+    assert!(sites.windows(2).all(|w| w[0].count >= w[1].count));
+    assert!(sites
+        .iter()
+        .all(|site| !site.stack.contains("addHeapObject")));
+
+    drop(vals);
+    assert_eq!(live_count(), Some(initial));
+}
@@ -0,0 +1,44 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_iterates_numbers = () => {
+    assert.deepStrictEqual([...wasm.js_iterator_countdown(3)], [3, 2, 1, 0]);
+    assert.deepStrictEqual(Array.from(wasm.js_iterator_boxed(4)), [0, 1, 4, 9]);
+
+    const iter = wasm.js_iterator_countdown(0);
+    assert.deepStrictEqual(iter.next(), { value: 0, done: false });
+    assert.deepStrictEqual(iter.next(), { value: undefined, done: true });
+    assert.deepStrictEqual(iter.next(), { value: undefined, done: true });
+};
+
+exports.js_iterates_strings = () => {
+    const words = [];
+    for (const word of wasm.js_iterator_words('hello iterator world'))
+        words.push(word);
+    assert.deepStrictEqual(words, ['hello', 'iterator', 'world']);
+};
+
+exports.js_iterates_structs = () => {
+    const points = [...wasm.js_iterator_points(3)];
+    assert.strictEqual(points.length, 3);
+    points.forEach((point, i) => {
+        assert.ok(point instanceof wasm.JsIteratorPoint);
+        assert.strictEqual(point.x, i);
+        point.free();
+    });
+};
+
+exports.js_stops_early = () => {
+    const before = wasm.js_iterator_heap_live_count();
+    for (const i of wasm.js_iterator_countdown(100)) {
+        if (i === 90)
+            break;
+    }
+    assert.strictEqual(wasm.js_iterator_heap_live_count(), before);
+
+    const iter = wasm.js_iterator_countdown(100);
+    iter.next();
+    assert.deepStrictEqual(iter.return(), { value: undefined, done: true });
+    assert.deepStrictEqual(iter.next(), { value: undefined, done: true });
+    assert.strictEqual(wasm.js_iterator_heap_live_count(), before);
+};
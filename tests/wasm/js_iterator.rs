@@ -0,0 +1,63 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsIterator;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/js_iterator.js")]
+extern "C" {
+    fn js_iterates_numbers();
+    fn js_iterates_strings();
+    fn js_iterates_structs();
+    fn js_stops_early();
+}
+
+#[wasm_bindgen]
+pub fn js_iterator_countdown(from: u32) -> JsIterator<u32> {
+    JsIterator::new((0..=from).rev())
+}
+
+#[wasm_bindgen]
+pub fn js_iterator_words(s: &str) -> JsIterator<String> {
+    let words = s.split_whitespace().map(String::from).collect::<Vec<_>>();
+    JsIterator::new(words)
+}
+
+#[wasm_bindgen]
+pub struct JsIteratorPoint {
+    pub x: u32,
+}
+
+#[wasm_bindgen]
+pub fn js_iterator_points(n: u32) -> JsIterator<JsIteratorPoint> {
+    JsIterator::new((0..n).map(|x| JsIteratorPoint { x }))
+}
+
+#[wasm_bindgen]
+pub fn js_iterator_boxed(n: u32) -> JsIterator<u32> {
+    let iter: Box<dyn Iterator<Item = u32>> = Box::new((0..n).map(|i| i * i));
+    JsIterator::new(iter)
+}
+
+#[wasm_bindgen]
+pub fn js_iterator_heap_live_count() -> u32 {
+    wasm_bindgen::anyref_heap_live_count()
+}
+
+#[wasm_bindgen_test]
+fn iterates_numbers() {
+    js_iterates_numbers();
+}
+
+#[wasm_bindgen_test]
+fn iterates_strings() {
+    js_iterates_strings();
+}
+
+#[wasm_bindgen_test]
+fn iterates_structs() {
+    js_iterates_structs();
+}
+
+#[wasm_bindgen_test]
+fn stops_early() {
+    js_stops_early();
+}
@@ -1,7 +1,9 @@
 #![cfg(target_arch = "wasm32")]
 
+extern crate futures;
 extern crate js_sys;
 extern crate wasm_bindgen;
+extern crate wasm_bindgen_futures;
 extern crate wasm_bindgen_test;
 extern crate wasm_bindgen_test_crate_a;
 extern crate wasm_bindgen_test_crate_b;
@@ -15,6 +17,7 @@ use wasm_bindgen::prelude::*;
 pub mod api;
 pub mod arg_names;
 pub mod char;
+pub mod class_hooks;
 pub mod classes;
 pub mod closures;
 pub mod comments;
@@ -23,9 +26,12 @@ pub mod duplicates;
 pub mod enums;
 #[path = "final.rs"]
 pub mod final_;
+pub mod generics;
 pub mod getters_and_setters;
+pub mod heap_snapshot;
 pub mod import_class;
 pub mod imports;
+pub mod js_iterator;
 pub mod js_objects;
 pub mod jscast;
 pub mod math;
@@ -33,9 +39,15 @@ pub mod node;
 pub mod option;
 pub mod optional_primitives;
 pub mod rethrow;
+pub mod send_js_value;
+#[cfg(feature = "serde-serialize")]
+pub mod serde_direct;
 pub mod simple;
 pub mod slice;
+pub mod statics;
 pub mod structural;
+pub mod traits;
+pub mod try_from_js_value;
 pub mod u64;
 pub mod validate_prt;
 pub mod variadic;
@@ -0,0 +1,46 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{thread_heap_stats, SendJsValue};
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn round_trip() {
+    let stats = thread_heap_stats();
+    let (handle, message) = SendJsValue::new(JsValue::from("hello"));
+    assert_eq!(handle.origin_thread(), stats.thread_id);
+
+    // The message hasn't been received yet.
+    let handle = handle.try_into_value().unwrap_err();
+
+    SendJsValue::receive(message).unwrap();
+    assert_eq!(
+        thread_heap_stats().pending_received,
+        stats.pending_received + 1
+    );
+
+    assert_eq!(handle.try_into_value().unwrap(), "hello");
+    assert_eq!(thread_heap_stats().pending_received, stats.pending_received);
+}
+
+#[wasm_bindgen_test]
+fn receive_rejects_other_messages() {
+    for message in vec![
+        JsValue::from(1),
+        JsValue::from("__wbindgen_send_js_value"),
+        js_sys::Array::of3(&"foo".into(), &1.into(), &2.into()).into(),
+    ] {
+        let err = SendJsValue::receive(message.clone()).unwrap_err();
+        assert_eq!(err, message);
+    }
+}
+
+#[wasm_bindgen_test]
+fn drop_frees_received_value() {
+    let before = thread_heap_stats();
+    let (handle, message) = SendJsValue::new(JsValue::from("hello"));
+    SendJsValue::receive(message).unwrap();
+    drop(handle);
+
+    let after = thread_heap_stats();
+    assert_eq!(after.pending_received, before.pending_received);
+    assert_eq!(after.live_count, before.live_count);
+}
@@ -0,0 +1,56 @@
+const assert = require('assert');
+
+exports.verify_serde_direct = function(a) {
+  assert.deepStrictEqual(a, {
+    a: 0,
+    b: 'foo',
+    c: undefined,
+    d: { a: 1 },
+    list: [1.5, -2],
+    map: new Map([[1, 'one'], [2, 'two']]),
+    small: -5,
+    big: BigInt('18446744073709551615'),
+    bytes: [1, 2, 3],
+    variants: [
+      'Unit',
+      { Newtype: 3 },
+      { Tuple: [1, 'x'] },
+      { Struct: { x: -1 } },
+    ],
+  });
+
+  return {
+    a: 2,
+    b: 'bar',
+    c: { a: 3 },
+    d: { a: 4 },
+    list: [0.25],
+    map: new Map([[3, 'three']]),
+    small: BigInt('-9223372036854775808'),
+    big: BigInt(7),
+    bytes: new Uint8Array([4, 5]),
+    variants: [
+      new Map([['Struct', { x: 2 }]]),
+      'Unit',
+      { Tuple: [5, 'y'] },
+    ],
+  };
+};
+
+exports.verify_serde_direct_configured = function(a) {
+  assert.deepStrictEqual(a, {
+    missing: null,
+    map: { a: 1, b: 2 },
+    bytes: [1, 2],
+    small: BigInt(1),
+  });
+  assert.strictEqual(Object.getPrototypeOf(a.map), Object.prototype);
+};
+
+exports.serde_direct_object_keys = function() {
+  return { '10': false, '-1': true };
+};
+
+exports.serde_direct_function = function() {
+  return () => {};
+};
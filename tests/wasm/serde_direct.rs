@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::serde_direct;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/serde_direct.js")]
+extern "C" {
+    fn verify_serde_direct(val: JsValue) -> JsValue;
+    fn verify_serde_direct_configured(val: JsValue);
+    fn serde_direct_object_keys() -> JsValue;
+    fn serde_direct_function() -> JsValue;
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SerdeDirectFoo {
+    a: u32,
+    b: String,
+    c: Option<SerdeDirectBar>,
+    d: SerdeDirectBar,
+    list: Vec<f64>,
+    map: BTreeMap<u32, String>,
+    small: i64,
+    big: u64,
+    bytes: Vec<u8>,
+    variants: Vec<SerdeDirectEnum>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SerdeDirectBar {
+    a: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub enum SerdeDirectEnum {
+    Unit,
+    Newtype(u32),
+    Tuple(u32, String),
+    Struct { x: i32 },
+}
+
+/// Serializes as a byte buffer rather than as a sequence of numbers.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[derive(Serialize)]
+pub struct SerdeDirectConfigured<'a> {
+    missing: Option<u32>,
+    map: BTreeMap<&'a str, u32>,
+    bytes: Bytes<'a>,
+    small: u64,
+}
+
+fn roundtrip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    JsValue::from_serde_direct(value)
+        .unwrap()
+        .into_serde_direct()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn serde_direct() {
+    let mut map = BTreeMap::new();
+    map.insert(1, "one".to_string());
+    map.insert(2, "two".to_string());
+
+    let ret = verify_serde_direct(
+        JsValue::from_serde_direct(&SerdeDirectFoo {
+            a: 0,
+            b: "foo".to_string(),
+            c: None,
+            d: SerdeDirectBar { a: 1 },
+            list: vec![1.5, -2.0],
+            map,
+            small: -5,
+            big: u64::max_value(),
+            bytes: vec![1, 2, 3],
+            variants: vec![
+                SerdeDirectEnum::Unit,
+                SerdeDirectEnum::Newtype(3),
+                SerdeDirectEnum::Tuple(1, "x".to_string()),
+                SerdeDirectEnum::Struct { x: -1 },
+            ],
+        })
+        .unwrap(),
+    );
+
+    let foo = ret.into_serde_direct::<SerdeDirectFoo>().unwrap();
+    let mut map = BTreeMap::new();
+    map.insert(3, "three".to_string());
+    assert_eq!(
+        foo,
+        SerdeDirectFoo {
+            a: 2,
+            b: "bar".to_string(),
+            c: Some(SerdeDirectBar { a: 3 }),
+            d: SerdeDirectBar { a: 4 },
+            list: vec![0.25],
+            map,
+            small: i64::min_value(),
+            big: 7,
+            bytes: vec![4, 5],
+            variants: vec![
+                SerdeDirectEnum::Struct { x: 2 },
+                SerdeDirectEnum::Unit,
+                SerdeDirectEnum::Tuple(5, "y".to_string()),
+            ],
+        }
+    );
+}
+
+#[wasm_bindgen_test]
+fn serde_direct_configured() {
+    let mut map = BTreeMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    let serializer = serde_direct::Serializer::new()
+        .serialize_maps_as_objects(true)
+        .serialize_bytes_as_arrays(true)
+        .serialize_missing_as_null(true)
+        .serialize_large_number_types_as_bigints(true);
+    let value = SerdeDirectConfigured {
+        missing: None,
+        map,
+        bytes: Bytes(&[1, 2]),
+        small: 1,
+    };
+    verify_serde_direct_configured(value.serialize(&serializer).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn serde_direct_roundtrip() {
+    assert_eq!(roundtrip(&"foo".to_string()), "foo");
+    assert_eq!(roundtrip(&'x'), 'x');
+    assert_eq!(roundtrip(&Some(true)), Some(true));
+    assert_eq!(roundtrip(&None::<u32>), None);
+    assert_eq!(roundtrip(&(1u8, -1i16, 0.5f32)), (1, -1, 0.5));
+    assert_eq!(roundtrip(&i64::min_value()), i64::min_value());
+    assert_eq!(roundtrip(&u128::max_value()), u128::max_value());
+    assert_eq!(roundtrip(&i128::min_value()), i128::min_value());
+    assert_eq!(roundtrip(&vec![vec![1u32], vec![]]), vec![vec![1], vec![]]);
+
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), SerdeDirectEnum::Newtype(1));
+    assert_eq!(roundtrip(&map), map);
+}
+
+#[wasm_bindgen_test]
+fn serde_direct_object_keys_are_parsed() {
+    let map = serde_direct_object_keys()
+        .into_serde_direct::<BTreeMap<i32, bool>>()
+        .unwrap();
+    let mut expected = BTreeMap::new();
+    expected.insert(-1, true);
+    expected.insert(10, false);
+    assert_eq!(map, expected);
+}
+
+#[wasm_bindgen_test]
+fn serde_direct_errors() {
+    assert!(JsValue::from("foo").into_serde_direct::<u32>().is_err());
+    assert!(JsValue::from(1.5).into_serde_direct::<u32>().is_err());
+    assert!(JsValue::from(-1).into_serde_direct::<u32>().is_err());
+    assert!(JsValue::NULL.into_serde_direct::<SerdeDirectBar>().is_err());
+    assert!(JsValue::from("Missing")
+        .into_serde_direct::<SerdeDirectEnum>()
+        .is_err());
+
+    let err = serde_direct_function()
+        .into_serde_direct::<String>()
+        .unwrap_err();
+    assert!(err.to_string().starts_with("unsupported JS value"));
+}
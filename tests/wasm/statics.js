@@ -0,0 +1,18 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.js_associated_constants = () => {
+    const red = wasm.StaticsColor.RED;
+    assert.ok(red instanceof wasm.StaticsColor);
+    assert.strictEqual(red.hex(), '#ff0000');
+    red.free();
+
+    assert.strictEqual(wasm.StaticsColor.CHANNELS, 3);
+    assert.strictEqual(wasm.StaticsColor.SECRET, undefined);
+};
+
+exports.js_static_accessors = () => {
+    assert.strictEqual(wasm.StaticsConfig.version, '1.0');
+    wasm.StaticsConfig.version = '2.0';
+    assert.strictEqual(wasm.StaticsConfig.version, '2.0');
+};
@@ -0,0 +1,61 @@
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/statics.js")]
+extern "C" {
+    fn js_associated_constants();
+    fn js_static_accessors();
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone)]
+pub struct StaticsColor {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[wasm_bindgen]
+impl StaticsColor {
+    /// Pure red.
+    #[wasm_bindgen(js_name = RED)]
+    pub const RED: StaticsColor = StaticsColor { r: 255, g: 0, b: 0 };
+    pub const CHANNELS: u32 = 3;
+    #[allow(dead_code)]
+    const SECRET: u32 = 42;
+
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+thread_local! {
+    static VERSION: RefCell<String> = RefCell::new("1.0".to_string());
+}
+
+#[wasm_bindgen]
+pub struct StaticsConfig;
+
+#[wasm_bindgen]
+impl StaticsConfig {
+    #[wasm_bindgen(getter, static)]
+    pub fn version() -> String {
+        VERSION.with(|v| v.borrow().clone())
+    }
+
+    #[wasm_bindgen(setter, static)]
+    pub fn set_version(version: String) {
+        VERSION.with(|v| *v.borrow_mut() = version);
+    }
+}
+
+#[wasm_bindgen_test]
+fn associated_constants() {
+    js_associated_constants();
+}
+
+#[wasm_bindgen_test]
+fn static_accessors() {
+    js_static_accessors();
+}
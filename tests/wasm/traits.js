@@ -0,0 +1,56 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+class Circle {
+    constructor(radius) {
+        this.radius = radius;
+    }
+
+    area() {
+        return 3 * this.radius * this.radius;
+    }
+
+    scale(factor) {
+        this.radius *= factor;
+    }
+
+    shapeName() {
+        return 'circle';
+    }
+}
+
+exports.js_exported_trait_objects = () => {
+    const square = wasm.traits_square(2);
+    assert.ok(square instanceof wasm.TraitsShape);
+    assert.strictEqual(square.area(), 4);
+    square.scale(1.5);
+    assert.strictEqual(square.area(), 9);
+    assert.strictEqual(square.shapeName(), 'square');
+    assert.strictEqual(wasm.traits_describe(square), 'square 9');
+    square.free();
+};
+
+exports.js_adapter_calls_js = () => {
+    const circle = new Circle(1);
+    assert.strictEqual(wasm.traits_scale_js(circle), 12);
+    assert.strictEqual(circle.radius, 2);
+
+    // Any object with the right methods works, not just class instances.
+    const area = wasm.traits_scale_js({
+        area() { return 5; },
+        scale(factor) { assert.strictEqual(factor, 2); },
+        shapeName() { return 'circle'; },
+    });
+    assert.strictEqual(area, 5);
+};
+
+exports.js_adapter_round_trip = () => {
+    const circle = new Circle(1);
+    const wrapped = wasm.traits_wrap(circle);
+    assert.ok(wrapped instanceof wasm.TraitsShape);
+    wrapped.scale(3);
+    assert.strictEqual(circle.radius, 3);
+    assert.strictEqual(wrapped.area(), 27);
+    assert.strictEqual(wasm.traits_describe(wrapped), 'circle 27');
+    wrapped.free();
+};
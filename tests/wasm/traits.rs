@@ -0,0 +1,71 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/traits.js")]
+extern "C" {
+    fn js_exported_trait_objects();
+    fn js_adapter_calls_js();
+    fn js_adapter_round_trip();
+}
+
+#[wasm_bindgen]
+pub trait TraitsShape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+    #[wasm_bindgen(js_name = shapeName)]
+    fn shape_name(&self) -> String;
+}
+
+struct Square(f64);
+
+impl TraitsShape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+
+    fn shape_name(&self) -> String {
+        "square".to_string()
+    }
+}
+
+#[wasm_bindgen]
+pub fn traits_square(side: f64) -> Box<dyn TraitsShape> {
+    Box::new(Square(side))
+}
+
+#[wasm_bindgen]
+pub fn traits_describe(shape: &Box<dyn TraitsShape>) -> String {
+    format!("{} {}", shape.shape_name(), shape.area())
+}
+
+#[wasm_bindgen]
+pub fn traits_scale_js(shape: JsTraitsShape) -> f64 {
+    let mut shape: Box<dyn TraitsShape> = shape.into();
+    assert_eq!(shape.shape_name(), "circle");
+    shape.scale(2.0);
+    shape.area()
+}
+
+#[wasm_bindgen]
+pub fn traits_wrap(shape: JsTraitsShape) -> Box<dyn TraitsShape> {
+    shape.into()
+}
+
+#[wasm_bindgen_test]
+fn exported_trait_objects() {
+    js_exported_trait_objects();
+}
+
+#[wasm_bindgen_test]
+fn adapter_calls_js() {
+    js_adapter_calls_js();
+}
+
+#[wasm_bindgen_test]
+fn adapter_round_trip() {
+    js_adapter_round_trip();
+}
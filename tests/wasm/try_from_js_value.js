@@ -0,0 +1,26 @@
+const wasm = require('wasm-bindgen-test.js');
+const assert = require('assert');
+
+exports.try_from_values = () => ({
+  int: 300,
+  fraction: 1.5,
+  negative: -1,
+  max_safe: Number.MAX_SAFE_INTEGER,
+  min_safe: Number.MIN_SAFE_INTEGER,
+  unsafe_int: 2 ** 53,
+  huge: 1e40,
+  str: '1',
+  emoji: '🦀',
+  two_chars: 'ab',
+  array: [1, 2, 3],
+  mixed: [1, 'x'],
+  floats: new Float64Array([0.5, 1.5]),
+  bigints: new BigInt64Array([BigInt(-1), BigInt(2)]),
+  data_view: new DataView(new ArrayBuffer(1)),
+});
+
+exports.make_try_from_struct = x => new wasm.TryFromStruct(x);
+
+exports.try_from_struct_is_disowned = obj => {
+  assert.strictEqual(obj.ptr, 0);
+};
@@ -0,0 +1,127 @@
+use std::convert::TryFrom;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::TryFromJsValue;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "tests/wasm/try_from_js_value.js")]
+extern "C" {
+    fn try_from_values() -> JsValue;
+    fn make_try_from_struct(x: u32) -> JsValue;
+    fn try_from_struct_is_disowned(obj: &JsValue);
+}
+
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct TryFromStruct {
+    x: u32,
+}
+
+#[wasm_bindgen]
+impl TryFromStruct {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: u32) -> TryFromStruct {
+        TryFromStruct { x }
+    }
+}
+
+#[wasm_bindgen]
+pub struct TryFromOther;
+
+fn value(name: &str) -> JsValue {
+    js_sys::Reflect::get(&try_from_values(), &name.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn numbers() {
+    assert_eq!(u32::try_from(value("int")).unwrap(), 300);
+    assert_eq!(i16::try_from(value("negative")).unwrap(), -1);
+    assert_eq!(f64::try_from(value("fraction")).unwrap(), 1.5);
+    assert_eq!(f32::try_from(value("fraction")).unwrap(), 1.5);
+
+    let err = u8::try_from(value("int")).unwrap_err();
+    assert_eq!(err.expected(), "an integer in the range of `u8`");
+    assert_eq!(err.into_value(), 300);
+    assert!(u32::try_from(value("fraction")).is_err());
+    assert!(u32::try_from(value("negative")).is_err());
+    assert!(u32::try_from(value("str")).is_err());
+    assert!(f32::try_from(value("huge")).is_err());
+    assert_eq!(u64::try_from(JsValue::from(300u64)).unwrap(), 300);
+    assert_eq!(u64::try_from(value("int")).unwrap(), 300);
+    assert_eq!(i64::try_from(value("max_safe")).unwrap(), (1 << 53) - 1);
+    assert_eq!(i64::try_from(value("min_safe")).unwrap(), 1 - (1 << 53));
+    let err = i64::try_from(value("unsafe_int")).unwrap_err();
+    assert_eq!(
+        err.expected(),
+        "a `BigInt` or safe integer in the range of `i64`"
+    );
+    assert!(i64::try_from(value("fraction")).is_err());
+    assert!(u64::try_from(value("negative")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn strings() {
+    assert_eq!(String::try_from(value("str")).unwrap(), "1");
+    assert_eq!(char::try_from(value("emoji")).unwrap(), '🦀');
+    assert!(char::try_from(value("two_chars")).is_err());
+    assert!(String::try_from(value("int")).is_err());
+    assert_eq!(bool::try_from(JsValue::TRUE).unwrap(), true);
+    assert!(bool::try_from(value("int")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn vecs() {
+    assert_eq!(Vec::<u8>::try_from(value("array")).unwrap(), [1, 2, 3]);
+    assert_eq!(Vec::<f64>::try_from(value("floats")).unwrap(), [0.5, 1.5]);
+    assert_eq!(Vec::<i64>::try_from(value("bigints")).unwrap(), [-1, 2]);
+    assert_eq!(
+        Vec::<JsValue>::try_from(value("mixed")).unwrap(),
+        [JsValue::from(1), JsValue::from("x")]
+    );
+
+    let err = Vec::<u32>::try_from(value("mixed")).unwrap_err();
+    assert_eq!(err.into_value(), "x");
+    assert!(Vec::<u32>::try_from(value("str")).is_err());
+    assert!(Vec::<u8>::try_from(value("data_view")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn options() {
+    assert_eq!(
+        Option::<u32>::try_from_js_value(JsValue::NULL).unwrap(),
+        None
+    );
+    assert_eq!(
+        Option::<u32>::try_from_js_value(JsValue::UNDEFINED).unwrap(),
+        None
+    );
+    assert_eq!(
+        Option::<u32>::try_from_js_value(value("int")).unwrap(),
+        Some(300)
+    );
+    assert!(Option::<u32>::try_from_js_value(value("str")).is_err());
+}
+
+#[wasm_bindgen_test]
+fn exported_structs() {
+    let obj = make_try_from_struct(3);
+    let s = TryFromStruct::try_from_js_value(obj.clone()).unwrap();
+    assert_eq!(s.x, 3);
+    try_from_struct_is_disowned(&obj);
+
+    // The object no longer owns a Rust value, so it can't be converted again.
+    assert!(TryFromStruct::try_from_js_value(obj).is_err());
+
+    let s = TryFromStruct::try_from_js_value(JsValue::from(TryFromStruct { x: 4 })).unwrap();
+    assert_eq!(s.x, 4);
+
+    let err = TryFromStruct::try_from_js_value(JsValue::from(TryFromOther)).unwrap_err();
+    assert_eq!(err.expected(), "an instance of `TryFromStruct`");
+    assert!(TryFromOther::try_from_js_value(err.into_value()).is_ok());
+    assert!(TryFromStruct::try_from_js_value(value("int")).is_err());
+
+    let s = TryFromStruct::try_from(make_try_from_struct(5)).unwrap();
+    assert_eq!(s.x, 5);
+    let err = TryFromStruct::try_from(JsValue::from(TryFromOther)).unwrap_err();
+    assert!(TryFromOther::try_from(err.into_value()).is_ok());
+}
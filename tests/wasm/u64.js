@@ -35,3 +35,25 @@ exports.js_works = () => {
     assert.deepStrictEqual(wasm.i64_slice([i64_min]), new BigInt64Array([i64_min]));
     assert.deepStrictEqual(wasm.u64_slice([u64_max]), new BigUint64Array([u64_max]));
 };
+
+exports.optional_i64_js_identity = a => a;
+exports.optional_u64_js_identity = a => a;
+
+exports.js_optional_works = () => {
+    const u64_max = BigInt('18446744073709551615');
+    const i64_min = BigInt('-9223372036854775808');
+    assert.strictEqual(wasm.optional_i64_rust_identity(undefined), undefined);
+    assert.strictEqual(wasm.optional_i64_rust_identity(null), undefined);
+    assert.strictEqual(wasm.optional_i64_rust_identity(BigInt('0')), BigInt('0'));
+    assert.strictEqual(wasm.optional_i64_rust_identity(BigInt('-1')), BigInt('-1'));
+    assert.strictEqual(wasm.optional_i64_rust_identity(i64_min), i64_min);
+    assert.strictEqual(wasm.optional_u64_rust_identity(undefined), undefined);
+    assert.strictEqual(wasm.optional_u64_rust_identity(BigInt('0')), BigInt('0'));
+    assert.strictEqual(wasm.optional_u64_rust_identity(u64_max), u64_max);
+};
+
+exports.i128_js_value = a => BigInt(a);
+
+exports.js_i128_works = (a, b) => {
+    assert.strictEqual(a, BigInt(b));
+};
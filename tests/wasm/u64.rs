@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
@@ -6,6 +8,11 @@ extern "C" {
     fn i64_js_identity(a: i64) -> i64;
     fn u64_js_identity(a: u64) -> u64;
     fn js_works();
+    fn optional_i64_js_identity(a: Option<i64>) -> Option<i64>;
+    fn optional_u64_js_identity(a: Option<u64>) -> Option<u64>;
+    fn js_optional_works();
+    fn i128_js_value(a: &str) -> JsValue;
+    fn js_i128_works(a: JsValue, b: &str);
 }
 
 #[wasm_bindgen]
@@ -67,3 +74,82 @@ pub fn u64_slice(a: &[u64]) -> Vec<u64> {
 fn works() {
     js_works();
 }
+
+#[wasm_bindgen]
+pub fn optional_i64_rust_identity(a: Option<i64>) -> Option<i64> {
+    optional_i64_js_identity(a)
+}
+
+#[wasm_bindgen]
+pub fn optional_u64_rust_identity(a: Option<u64>) -> Option<u64> {
+    optional_u64_js_identity(a)
+}
+
+#[wasm_bindgen_test]
+fn optional_works() {
+    for &a in [
+        None,
+        Some(0),
+        Some(-1),
+        Some(i64::min_value()),
+        Some(i64::max_value()),
+    ]
+    .iter()
+    {
+        assert_eq!(optional_i64_js_identity(a), a);
+    }
+    for &a in [None, Some(0), Some(u64::max_value())].iter() {
+        assert_eq!(optional_u64_js_identity(a), a);
+    }
+    js_optional_works();
+}
+
+#[wasm_bindgen_test]
+fn i128_to_js() {
+    js_i128_works(JsValue::from(0i128), "0");
+    js_i128_works(JsValue::from(-1i128), "-1");
+    js_i128_works(
+        JsValue::from(i128::min_value()),
+        "-170141183460469231731687303715884105728",
+    );
+    js_i128_works(
+        JsValue::from(u128::max_value()),
+        "340282366920938463463374607431768211455",
+    );
+    js_i128_works(JsValue::from(i64::min_value()), "-9223372036854775808");
+    js_i128_works(JsValue::from(u64::max_value()), "18446744073709551615");
+}
+
+#[wasm_bindgen_test]
+fn i128_from_js() {
+    let min = i128_js_value("-170141183460469231731687303715884105728");
+    assert_eq!(i128::try_from(min.clone()).unwrap(), i128::min_value());
+    assert!(u128::try_from(min.clone()).is_err());
+    assert!(i64::try_from(min).is_err());
+
+    let max = i128_js_value("340282366920938463463374607431768211455");
+    assert_eq!(u128::try_from(max.clone()).unwrap(), u128::max_value());
+    assert!(i128::try_from(max.clone()).is_err());
+
+    let too_big = i128_js_value("340282366920938463463374607431768211456");
+    assert_eq!(
+        i128::try_from(too_big.clone()).unwrap_err().into_value(),
+        too_big
+    );
+    assert!(u128::try_from(too_big).is_err());
+
+    assert_eq!(i64::try_from(i128_js_value("-1")).unwrap(), -1);
+    assert_eq!(
+        u64::try_from(i128_js_value("18446744073709551615")).unwrap(),
+        u64::max_value()
+    );
+    assert!(u64::try_from(i128_js_value("18446744073709551616")).is_err());
+    assert!(u64::try_from(i128_js_value("-1")).is_err());
+
+    // Numbers are converted if they're safe integers, strings never are.
+    assert_eq!(i64::try_from(JsValue::from(-1)).unwrap(), -1);
+    assert_eq!(u128::try_from(JsValue::from(1)).unwrap(), 1);
+    assert!(u64::try_from(JsValue::from(-1)).is_err());
+    assert!(i64::try_from(JsValue::from(1.5)).is_err());
+    assert!(u128::try_from(JsValue::from("1")).is_err());
+}